@@ -0,0 +1,27 @@
+use aoc::day1::*;
+use aoc::testgen::random_calories;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// 100k boxes of up to 15 snacks each.
+fn boxes() -> Vec<CalorieBox> {
+    input_generator(&random_calories(1, 100000, 15)).unwrap()
+}
+
+/// Sort the boxes as before totals were cached, summing both boxes on every comparison.
+fn largest_uncached(input: &[CalorieBox], count: usize) -> Vec<CalorieBox> {
+    let mut boxes = Vec::from(input);
+    boxes.sort_by_key(|b| std::cmp::Reverse(b.total()));
+    boxes.truncate(count);
+    boxes
+}
+
+fn sorting(c: &mut Criterion) {
+    let boxes = boxes();
+    let mut group = c.benchmark_group("day1 largest boxes");
+    group.bench_function("uncached", |b| b.iter(|| largest_uncached(black_box(&boxes), 3)));
+    group.bench_function("cached", |b| b.iter(|| get_largest_boxes(black_box(&boxes), 3)));
+    group.finish();
+}
+
+criterion_group!(benches, sorting);
+criterion_main!(benches);
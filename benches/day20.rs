@@ -0,0 +1,21 @@
+use aoc::day20::*;
+use aoc::testgen::random_numbers;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// A list the size of the real puzzle input, with repeated values and a single zero.
+fn numbers() -> Vec<i64> {
+    let numbers = input_generator(&random_numbers(20, 5000)).unwrap();
+    numbers.into_iter().map(|n| n * DECRYPTION_KEY).collect()
+}
+
+fn mixing(c: &mut Criterion) {
+    let numbers = numbers();
+    let mut group = c.benchmark_group("day20 mix");
+    group.sample_size(10);
+    group.bench_function("vec", |b| b.iter(|| mix_naive(black_box(&numbers), 10)));
+    group.bench_function("blocks", |b| b.iter(|| mix(black_box(&numbers), 10)));
+    group.finish();
+}
+
+criterion_group!(benches, mixing);
+criterion_main!(benches);
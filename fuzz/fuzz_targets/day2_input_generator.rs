@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    aoc::day2::input_generator_part1(&input);
+    aoc::day2::input_generator_part2(&input);
+});
@@ -0,0 +1,94 @@
+use core::fmt;
+
+use alloc::string::String;
+
+/// The answer to a part of a puzzle, keeping numbers as numbers so they can be compared without
+/// going through a String. Answers are printed by the runner through their Display.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::answer::Answer;
+/// # use aoc::{day1, day2, day3};
+/// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+/// let boxes = day1::input_generator(given_calories).unwrap();
+/// assert_eq!(day1::solve_part1(&boxes), Answer::Int(24000));
+/// assert_eq!(day1::solve_part2(&boxes), Answer::Int(45000));
+///
+/// let given_plays = "A Y\nB X\nC Z";
+/// assert_eq!(day2::solve_part1(&day2::input_generator_part1(given_plays)), Answer::Int(15));
+/// assert_eq!(day2::solve_part2(&day2::input_generator_part2(given_plays)), Answer::Int(12));
+///
+/// let lines = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg
+/// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+/// assert_eq!(day3::solve_part1(&day3::input_generator_part1(lines)), Answer::Int(157));
+/// assert_eq!(day3::solve_part2(&day3::input_generator_part2(lines)), Answer::Int(70));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    /// Format the answer as it should be submitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::answer::*;
+    /// assert_eq!(Answer::Int(-15).to_string(), "-15");
+    /// assert_eq!(Answer::Text("2=-1=0".to_string()).to_string(), "2=-1=0");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Answer {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Answer {
+        Answer::Int(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+/// An answer along with the day and part it answers, for machine readable output.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AnswerRecord {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+}
+
+#[cfg(feature = "serde")]
+impl AnswerRecord {
+    /// Format the record as a single line of JSON.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::answer::*;
+    /// let record = AnswerRecord { day: 1, part: 1, answer: Answer::Int(24000).to_string() };
+    /// let json = record.to_json();
+    /// assert_eq!(json, r#"{"day":1,"part":1,"answer":"24000"}"#);
+    /// assert_eq!(serde_json::from_str::<AnswerRecord>(&json).unwrap(), record);
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records always serialize")
+    }
+}
@@ -0,0 +1,90 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+
+/// Convert a number written in a balanced base into an integer, where `digits` maps each digit
+/// character to its value.
+///
+/// # Panics
+///
+/// If the string contains a character that isn't in `digits`, the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::balanced::*;
+/// let ternary = [('-', -1), ('0', 0), ('+', 1)];
+/// assert_eq!(from_balanced("+-0", 3, &ternary), 6);
+/// assert_eq!(from_balanced("-+", 3, &ternary), -2);
+/// ```
+pub fn from_balanced(s: &str, base: i128, digits: &[(char, i128)]) -> i128 {
+    s.chars().fold(0, |n, c| {
+        let (_, digit) =
+            digits.iter().find(|(d, _)| *d == c).unwrap_or_else(|| panic!("invalid digit: {c}"));
+        n * base + digit
+    })
+}
+
+/// Convert an integer into a balanced base, where `digits` maps each digit character to its value
+/// and covers a full set of remainders. Whenever the remainder in a position is larger than the
+/// largest digit, the base is subtracted from it and one is carried into the next position up.
+///
+/// # Panics
+///
+/// If `base` is less than 2, which would never reduce `n` to zero, or if `digits` has no digit for
+/// some remainder, the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::balanced::*;
+/// let ternary = [('-', -1), ('0', 0), ('+', 1)];
+/// assert_eq!(to_balanced(6, 3, &ternary), "+-0");
+/// assert_eq!(to_balanced(-2, 3, &ternary), "-+");
+/// for n in -1000..1000 {
+///     assert_eq!(from_balanced(&to_balanced(n, 3, &ternary), 3, &ternary), n);
+/// }
+///
+/// let snafu = [('=', -2), ('-', -1), ('0', 0), ('1', 1), ('2', 2)];
+/// assert_eq!(to_balanced(0, 5, &snafu), "0");
+/// assert_eq!(to_balanced(4890, 5, &snafu), "2=-1=0");
+/// for n in [1, 5, 12, 2022, 314159265, i128::MAX / 2, -7] {
+///     assert_eq!(from_balanced(&to_balanced(n, 5, &snafu), 5, &snafu), n);
+/// }
+///
+/// // Both ends of i128 convert without overflowing.
+/// let min = "-20=2-20==-122=21-1=--202011=02=21-===212-10-=22=211-1-2";
+/// assert_eq!(to_balanced(i128::MIN, 5, &snafu), min);
+/// let max = "1=02=1=0221-==2=-1-211=0=0--20=2=-1222=-=1-012==2=--1-02";
+/// assert_eq!(to_balanced(i128::MAX, 5, &snafu), max);
+/// ```
+///
+/// ```rust,should_panic
+/// # use aoc::balanced::*;
+/// to_balanced(6, 1, &[('0', 0)]);
+/// ```
+pub fn to_balanced(mut n: i128, base: i128, digits: &[(char, i128)]) -> String {
+    assert!(base >= 2, "base must be at least 2, not {base}");
+    let max = digits.iter().map(|(_, value)| *value).max().unwrap_or(0);
+    let digit = |value: i128| {
+        digits
+            .iter()
+            .find(|(_, v)| *v == value)
+            .map(|(c, _)| *c)
+            .unwrap_or_else(|| panic!("no digit for {value}"))
+    };
+    if n == 0 {
+        return digit(0).to_string();
+    }
+    let mut out = vec![];
+    while n != 0 {
+        // Dividing first, then carrying, keeps `n` in range even at the ends of i128.
+        let mut remainder = n.rem_euclid(base);
+        n = n.div_euclid(base);
+        if remainder > max {
+            remainder -= base;
+            n += 1;
+        }
+        out.push(digit(remainder));
+    }
+    out.iter().rev().collect()
+}
@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Find the most common element and the number of times it appears, or None if there are no
+/// elements. Ties go to whichever element reached the highest count first.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::collections::*;
+/// assert_eq!(most_common(vec!['a', 'b', 'c', 'b', 'a', 'b']), Some(('b', 3)));
+/// assert_eq!(most_common(vec![3, 1, 1, 3]), Some((1, 2)));
+/// assert_eq!(most_common(Vec::<u8>::new()), None);
+/// ```
+pub fn most_common<T: Eq + Hash + Clone>(items: impl IntoIterator<Item = T>) -> Option<(T, usize)> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+    let mut best: Option<(T, usize)> = None;
+    for item in items {
+        let count = counts.entry(item.clone()).or_insert(0);
+        *count += 1;
+        if best.as_ref().is_none_or(|(_, most)| *count > *most) {
+            best = Some((item, *count));
+        }
+    }
+    best
+}
@@ -0,0 +1,160 @@
+use std::collections::VecDeque;
+
+use crate::grid::Grid;
+use crate::point::Point;
+
+/// A direction in three dimensions, as a unit vector along one of the axes.
+pub type Vec3 = [i32; 3];
+
+/// Reverse a direction.
+pub fn neg(v: Vec3) -> Vec3 {
+    [-v[0], -v[1], -v[2]]
+}
+
+/// A face of the cube, along with the way it is oriented once folded. `right` and `down` are the
+/// directions the map's x and y axes point in on that face, and `normal` points out of the cube.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Face {
+    pub origin: Point,
+    pub normal: Vec3,
+    pub right: Vec3,
+    pub down: Vec3,
+}
+
+impl Face {
+    /// The direction a facing points in once folded, where 0 is right, 1 is down, 2 is left, and
+    /// 3 is up on the map.
+    pub fn direction(&self, facing: u8) -> Vec3 {
+        match facing {
+            0 => self.right,
+            1 => self.down,
+            2 => neg(self.right),
+            _ => neg(self.down),
+        }
+    }
+
+    /// The direction running along the edge crossed when leaving with the given facing.
+    pub fn along(&self, facing: u8) -> Vec3 {
+        if facing.is_multiple_of(2) {
+            self.down
+        } else {
+            self.right
+        }
+    }
+}
+
+/// The faces of a cube, as laid out in a net on a map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CubeLayout {
+    pub size: i64,
+    pub faces: Vec<Face>,
+}
+
+impl CubeLayout {
+    /// Get the face containing a point.
+    pub fn face_at(&self, point: Point) -> Option<&Face> {
+        let origin = Point::new(
+            point.x.div_euclid(self.size) * self.size,
+            point.y.div_euclid(self.size) * self.size,
+        );
+        self.faces.iter().find(|f| f.origin == origin)
+    }
+
+    /// Get the face sharing the edge crossed when leaving a face with the given facing, once the
+    /// net is folded.
+    pub fn adjacent(&self, face: &Face, facing: u8) -> Option<&Face> {
+        self.faces.iter().find(|f| f.normal == face.direction(facing))
+    }
+}
+
+/// Detect the faces of a cube net in a map, where spaces are not part of the net, and fold the
+/// net to find the orientation of each face. Starting from the first face in the top row, each
+/// face sharing an edge in the net is folded over that edge, which works for any shape of net.
+/// A map that isn't a cube net gives a layout without exactly six faces, or with faces folded
+/// onto the same side of the cube.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::cube::*;
+/// # use aoc::grid::Grid;
+/// # use aoc::point::Point;
+/// // Draw a net with each face as a single character, then scale it up to the size of a face.
+/// let scale = |net: &[&str], size| {
+///     let rows = net
+///         .iter()
+///         .map(|row| row.chars().flat_map(|c| vec![c; size]).collect::<Vec<_>>())
+///         .flat_map(|row| vec![row; size])
+///         .collect::<Vec<_>>();
+///     Grid::from_rows(&rows, ' ')
+/// };
+///
+/// // The net from the puzzle's example, with faces numbered as in the puzzle.
+/// let layout = detect_faces(&scale(&["  1", "234", "  56"], 4), 4);
+/// assert_eq!(layout.faces.len(), 6);
+/// let origins = [(2, 0), (0, 1), (1, 1), (2, 1), (2, 2), (3, 2)];
+/// let face = |n: usize| {
+///     let (x, y) = origins[n - 1];
+///     layout.face_at(Point::new(x * 4, y * 4)).unwrap()
+/// };
+/// let adjacent = |n, facing| layout.adjacent(face(n), facing).unwrap();
+/// // Facings are 0 for right, 1 for down, 2 for left, and 3 for up.
+/// assert_eq!(adjacent(1, 0), face(6));
+/// assert_eq!(adjacent(1, 2), face(3));
+/// assert_eq!(adjacent(1, 3), face(2));
+/// assert_eq!(adjacent(4, 0), face(6));
+/// assert_eq!(adjacent(5, 1), face(2));
+/// assert_eq!(adjacent(5, 2), face(3));
+/// assert_eq!(adjacent(6, 3), face(4));
+/// assert_eq!(face(1).normal.map(|n| -n), face(5).normal);
+///
+/// // The net shared by most real inputs, at full size.
+/// let layout = detect_faces(&scale(&[" AB", " C", "DE", "F"], 50), 50);
+/// assert_eq!(layout.faces.len(), 6);
+/// let origins = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+/// let face = |c: char| {
+///     let (x, y) = origins[(c as u8 - b'A') as usize];
+///     layout.face_at(Point::new(x * 50, y * 50)).unwrap()
+/// };
+/// let adjacent = |c, facing| layout.adjacent(face(c), facing).unwrap();
+/// assert_eq!(adjacent('A', 3), face('F'));
+/// assert_eq!(adjacent('A', 2), face('D'));
+/// assert_eq!(adjacent('B', 3), face('F'));
+/// assert_eq!(adjacent('B', 0), face('E'));
+/// assert_eq!(adjacent('B', 1), face('C'));
+/// assert_eq!(adjacent('C', 2), face('D'));
+/// assert_eq!(adjacent('E', 1), face('F'));
+///
+/// assert_eq!(detect_faces(&scale(&["AB"], 2), 2).faces.len(), 2);
+/// ```
+pub fn detect_faces(map: &Grid<char>, face_size: usize) -> CubeLayout {
+    let size = face_size as i64;
+    let on_net = |point: Point| map.get(point).is_some_and(|c| *c != ' ');
+    let mut columns = (0..map.width() as i64).step_by(face_size.max(1));
+    let Some(x) = columns.find(|x| on_net(Point::new(*x, 0))) else {
+        return CubeLayout { size, faces: vec![] };
+    };
+    let first =
+        Face { origin: Point::new(x, 0), normal: [0, 0, 1], right: [1, 0, 0], down: [0, 1, 0] };
+    let mut faces = vec![first];
+    let mut queue = VecDeque::from([first]);
+    while let Some(face) = queue.pop_front() {
+        let (n, r, d) = (face.normal, face.right, face.down);
+        let folds = [
+            (Point::new(size, 0), Face { normal: r, right: neg(n), ..face }),
+            (Point::new(0, size), Face { normal: d, down: neg(n), ..face }),
+            (Point::new(-size, 0), Face { normal: neg(r), right: n, ..face }),
+            (Point::new(0, -size), Face { normal: neg(d), down: n, ..face }),
+        ];
+        for (offset, folded) in folds {
+            let origin = face.origin + offset;
+            if !on_net(origin) || faces.iter().any(|f| f.origin == origin) {
+                continue;
+            }
+            let folded = Face { origin, ..folded };
+            faces.push(folded);
+            queue.push_back(folded);
+        }
+    }
+    CubeLayout { size, faces }
+}
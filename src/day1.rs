@@ -1,7 +1,21 @@
 use aoc_runner_derive::*;
 
-/// A box storing all meals, snacks, etc. and the position the box is in within the elves.
-#[derive(Debug, Clone, PartialEq, Eq)]
+use anyhow::Result;
+
+use crate::answer::Answer;
+use crate::parse::ParseContext;
+
+/// A box storing all meals, snacks, etc. and the position the box is in within the elves. The
+/// default box is empty, at position 0.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// assert_eq!(CalorieBox::default(), CalorieBox::new(0, &[]));
+/// assert_eq!(CalorieBox::default().total(), 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CalorieBox {
     position: usize,
     calories: Vec<u32>,
@@ -17,6 +31,112 @@ impl CalorieBox {
     pub fn total(&self) -> u32 {
         self.calories.iter().sum()
     }
+
+    /// Iterate over the calories of each snack within the box.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day1::*;
+    /// let calorie_box = CalorieBox::new(0, &[1000, 2000, 3000]);
+    /// assert_eq!(calorie_box.iter().sum::<u32>(), calorie_box.total());
+    /// assert_eq!(calorie_box.iter().max(), Some(&3000));
+    ///
+    /// let mut total = 0;
+    /// for calories in &calorie_box {
+    ///     total += calories;
+    /// }
+    /// assert_eq!(total, 6000);
+    /// ```
+    pub fn iter(&self) -> std::slice::Iter<'_, u32> {
+        self.calories.iter()
+    }
+
+    /// Pair the box with its total, so that the total is only summed once when it's needed
+    /// repeatedly, such as when sorting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day1::*;
+    /// let calorie_box = CalorieBox::new(0, &[1000, 2000, 3000]);
+    /// let totaled = calorie_box.with_total();
+    /// assert_eq!(totaled.total, calorie_box.total());
+    /// assert_eq!(totaled.calorie_box, &calorie_box);
+    /// ```
+    pub fn with_total(&self) -> TotaledBox<'_> {
+        TotaledBox { total: self.total(), calorie_box: self }
+    }
+}
+
+/// A CalorieBox along with its precomputed total.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TotaledBox<'a> {
+    pub total: u32,
+    pub calorie_box: &'a CalorieBox,
+}
+
+impl<'a> IntoIterator for &'a CalorieBox {
+    type Item = &'a u32;
+    type IntoIter = std::slice::Iter<'a, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A builder for a CalorieBox, adding calories one at a time.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// let calorie_box = CalorieBoxBuilder::default()
+///     .at(3)
+///     .add_calorie(7000)
+///     .add_calorie(8000)
+///     .add_calorie(9000)
+///     .build();
+/// assert_eq!(calorie_box.total(), 24000);
+/// assert_eq!(calorie_box, CalorieBox::new(3, &[7000, 8000, 9000]));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CalorieBoxBuilder {
+    position: usize,
+    calories: Vec<u32>,
+}
+
+impl CalorieBoxBuilder {
+    /// Set the position of the box within the elves.
+    pub fn at(mut self, position: usize) -> CalorieBoxBuilder {
+        self.position = position;
+        self
+    }
+
+    /// Add a meal, snack, etc. to the box.
+    pub fn add_calorie(mut self, calorie: u32) -> CalorieBoxBuilder {
+        self.calories.push(calorie);
+        self
+    }
+
+    pub fn build(self) -> CalorieBox {
+        CalorieBox { position: self.position, calories: self.calories }
+    }
+}
+
+impl std::fmt::Display for CalorieBox {
+    /// Format the box with its position, number of snacks, and total calories.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day1::*;
+    /// let calorie_box = CalorieBox::new(2, &[5000, 6000]);
+    /// assert_eq!(calorie_box.to_string(), "Elf #2: 2 snacks, 11000 cal");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Elf #{}: {} snacks, {} cal", self.position, self.calories.len(), self.total())
+    }
 }
 
 /// Given an input in the form of numbers across lines, transform that input into a series of
@@ -27,7 +147,7 @@ impl CalorieBox {
 /// ```rust
 /// # use aoc::day1::*;
 /// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
-/// let boxes = input_generator(given_calories);
+/// let boxes = input_generator(given_calories).unwrap();
 /// let manual = vec![
 ///     CalorieBox::new(0, &[1000, 2000, 3000]),
 ///     CalorieBox::new(1, &[4000]),
@@ -36,21 +156,31 @@ impl CalorieBox {
 ///     CalorieBox::new(4, &[10000]),
 /// ];
 /// assert_eq!(boxes, manual);
+///
+/// let error = input_generator("1000\n\n12a").unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Was given an invalid value on line 3: \"12a\" (invalid digit found in string)"
+/// );
 /// ```
 #[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> Vec<CalorieBox> {
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 1, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Vec<CalorieBox>> {
     let mut boxes = vec![];
     let mut calories = vec![];
-    for line in input.lines() {
+    for (i, line) in input.lines().enumerate() {
         if line.is_empty() {
             boxes.push(CalorieBox { position: boxes.len(), calories });
             calories = vec![];
         } else {
-            calories.push(line.parse().expect("couldn't parse"));
+            calories.push(ParseContext { line: i + 1 }.parse(line)?);
         }
     }
     boxes.push(CalorieBox { position: boxes.len(), calories });
-    boxes
+    Ok(boxes)
 }
 
 /// Get the largest box from a slice of CalorieBox.
@@ -64,29 +194,118 @@ pub fn input_generator(input: &str) -> Vec<CalorieBox> {
 /// ```rust
 /// # use aoc::day1::*;
 /// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
-/// let boxes = input_generator(given_calories);
+/// let boxes = input_generator(given_calories).unwrap();
 /// assert_eq!(get_largest_box(&boxes).total(), 24000);
 /// ```
 pub fn get_largest_box(input: &[CalorieBox]) -> &CalorieBox {
     input.iter().max_by(|a, b| a.total().partial_cmp(&b.total()).unwrap()).expect("empty input")
 }
 
-/// Get the largest set of boxes from a slice of CalorieBox.
+/// Get the largest set of boxes from a slice of CalorieBox. Each total is computed once with
+/// [`CalorieBox::with_total`] rather than on every comparison. Boxes with equal totals keep their
+/// original order.
 ///
 /// # Example
 ///
 /// ```rust
 /// # use aoc::day1::*;
 /// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
-/// let boxes = input_generator(given_calories);
+/// let boxes = input_generator(given_calories).unwrap();
 /// let total = sum_boxes(&get_largest_boxes(&boxes, 3));
 /// assert_eq!(total, 45000);
 /// ```
 pub fn get_largest_boxes(input: &[CalorieBox], count: usize) -> Vec<CalorieBox> {
-    let mut boxes = Vec::from(input);
-    boxes.sort_by(|a, b| b.total().partial_cmp(&a.total()).unwrap());
-    boxes.truncate(count);
-    boxes
+    let mut boxes = input.iter().map(CalorieBox::with_total).collect::<Vec<_>>();
+    boxes.sort_by_key(|b| std::cmp::Reverse(b.total));
+    boxes.into_iter().take(count).map(|b| b.calorie_box.clone()).collect()
+}
+
+/// Get the largest set of boxes from a slice of CalorieBox as in [`get_largest_boxes`], computing
+/// totals and sorting in parallel. Boxes with equal totals keep their original order, so the
+/// result is always the same as the sequential version.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// # #[cfg(feature = "testgen")] {
+/// use aoc::testgen::random_calories;
+///
+/// // Single snacks give many equal totals, checking that ties stay in order.
+/// for (seed, max_snacks) in [(1, 1), (2, 8)] {
+///     let boxes = input_generator(&random_calories(seed, 10000, max_snacks)).unwrap();
+///     for count in [0, 1, 3, 100, 20000] {
+///         assert_eq!(get_largest_boxes_par(&boxes, count), get_largest_boxes(&boxes, count));
+///     }
+/// }
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub fn get_largest_boxes_par(input: &[CalorieBox], count: usize) -> Vec<CalorieBox> {
+    use rayon::prelude::*;
+
+    let mut totals = input
+        .par_iter()
+        .enumerate()
+        .map(|(i, b)| (std::cmp::Reverse(b.total()), i))
+        .collect::<Vec<_>>();
+    totals.par_sort_unstable();
+    totals.into_iter().take(count).map(|(_, i)| input[i].clone()).collect()
+}
+
+/// Get the largest set of boxes from a slice of CalorieBox as in [`get_largest_boxes`], keeping
+/// only the largest `count` boxes seen so far in a heap rather than sorting every box. Boxes with
+/// equal totals keep their original order, so the result is always the same as the sorted
+/// version.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// # use aoc::testutil::assert_same;
+/// let inputs = [
+///     "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000",
+///     "5\n\n5\n\n5\n\n1\n\n5",
+///     "7",
+/// ];
+/// for count in [0, 1, 3, 10, usize::MAX] {
+///     assert_same(
+///         &inputs,
+///         |s| get_largest_boxes(&input_generator(s).unwrap(), count),
+///         |s| get_largest_boxes_heap(&input_generator(s).unwrap(), count),
+///     );
+/// }
+/// ```
+pub fn get_largest_boxes_heap(input: &[CalorieBox], count: usize) -> Vec<CalorieBox> {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    // The smallest box kept is at the top of the heap, with later boxes losing ties. No more boxes
+    // than there are can be kept, however large the count asked for.
+    let mut heap = BinaryHeap::with_capacity(count.min(input.len()) + 1);
+    for (i, b) in input.iter().enumerate() {
+        heap.push(Reverse((b.total(), Reverse(i))));
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|Reverse((_, Reverse(i)))| input[i].clone()).collect()
+}
+
+/// Find the box that was at the given position within the elves, even once the boxes have been
+/// reordered.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+/// let boxes = get_largest_boxes(&input_generator(given_calories).unwrap(), 5);
+/// assert_eq!(find_by_position(&boxes, 2).map(CalorieBox::total), Some(11000));
+/// assert_eq!(find_by_position(&boxes, 5), None);
+/// ```
+pub fn find_by_position(boxes: &[CalorieBox], position: usize) -> Option<&CalorieBox> {
+    boxes.iter().find(|b| b.position == position)
 }
 
 /// Get the sum of all calories in the given boxes.
@@ -96,7 +315,7 @@ pub fn get_largest_boxes(input: &[CalorieBox], count: usize) -> Vec<CalorieBox>
 /// ```rust
 /// # use aoc::day1::*;
 /// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
-/// let boxes = input_generator(given_calories);
+/// let boxes = input_generator(given_calories).unwrap();
 /// let total = sum_boxes(&get_largest_boxes(&boxes, 3));
 /// assert_eq!(total, 45000);
 /// ```
@@ -104,14 +323,114 @@ pub fn sum_boxes(input: &[CalorieBox]) -> u32 {
     input.iter().map(|b| b.total()).sum::<u32>()
 }
 
+/// Get the mean of the totals of the given boxes, or None if there are no boxes.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+/// let boxes = input_generator(given_calories).unwrap();
+/// assert_eq!(mean_total(&boxes), Some(11000.0));
+/// assert_eq!(mean_total(&[]), None);
+/// ```
+pub fn mean_total(boxes: &[CalorieBox]) -> Option<f64> {
+    if boxes.is_empty() {
+        return None;
+    }
+    Some(boxes.iter().map(|b| f64::from(b.total())).sum::<f64>() / boxes.len() as f64)
+}
+
+/// Get the median of the totals of the given boxes, or None if there are no boxes. With an even
+/// number of boxes, the two middle totals are averaged.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day1::*;
+/// let given_calories = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+/// let boxes = input_generator(given_calories).unwrap();
+/// assert_eq!(median_total(&boxes), Some(10000.0));
+/// assert_eq!(median_total(&boxes[1..]), Some(10500.0));
+/// assert_eq!(median_total(&[]), None);
+/// ```
+pub fn median_total(boxes: &[CalorieBox]) -> Option<f64> {
+    let mut totals = boxes.iter().map(|b| b.total()).collect::<Vec<_>>();
+    totals.sort_unstable();
+    let middle = totals.len() / 2;
+    match totals.len() {
+        0 => None,
+        n if n.is_multiple_of(2) => {
+            Some((f64::from(totals[middle - 1]) + f64::from(totals[middle])) / 2.0)
+        }
+        _ => Some(f64::from(totals[middle])),
+    }
+}
+
+/// With the `tracing` feature, every generator and solver records a span with its day, part, and
+/// the length of its input.
+///
+/// ```rust
+/// # #[cfg(feature = "tracing")]
+/// # {
+/// # use aoc::day1::*;
+/// use std::sync::{Arc, Mutex};
+/// use tracing::{span, Event, Metadata, Subscriber};
+///
+/// struct Spans(Arc<Mutex<Vec<String>>>);
+///
+/// impl Subscriber for Spans {
+///     fn enabled(&self, _: &Metadata<'_>) -> bool {
+///         true
+///     }
+///     fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+///         let mut spans = self.0.lock().unwrap();
+///         spans.push(format!("{} {:?}", span.metadata().name(), span.values()));
+///         span::Id::from_u64(spans.len() as u64)
+///     }
+///     fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+///     fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+///     fn event(&self, _: &Event<'_>) {}
+///     fn enter(&self, _: &span::Id) {}
+///     fn exit(&self, _: &span::Id) {}
+/// }
+///
+/// let spans = Arc::new(Mutex::new(vec![]));
+/// tracing::subscriber::with_default(Spans(spans.clone()), || {
+///     solve_part1(&input_generator("1000\n2000\n\n4000").unwrap())
+/// });
+/// let spans = spans.lock().unwrap();
+/// assert_eq!(spans.len(), 2);
+/// assert!(spans[0].starts_with("input_generator"));
+/// assert!(spans[1].starts_with("solve_part1"));
+/// assert!(spans[1].contains("day: 1") && spans[1].contains("part: 1"));
+/// assert!(spans[1].contains("input_len: 2"));
+/// # }
+/// ```
 #[doc(hidden)]
 #[aoc(day1, part1)]
-pub fn solve_part1(input: &[CalorieBox]) -> String {
-    get_largest_box(input).total().to_string()
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 1, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[CalorieBox]) -> Answer {
+    get_largest_box(input).total().into()
 }
 
 #[doc(hidden)]
 #[aoc(day1, part2)]
-pub fn solve_part2(input: &[CalorieBox]) -> String {
-    sum_boxes(&get_largest_boxes(input, 3)).to_string()
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 1, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[CalorieBox]) -> Answer {
+    sum_boxes(&get_largest_boxes(input, 3)).into()
+}
+
+solution! {
+    day: 1,
+    try_generator: input_generator,
+    example: "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000",
+    solve_part1: Answer::Int(24000),
+    solve_part2: Answer::Int(45000),
 }
@@ -0,0 +1,329 @@
+use std::cmp::Ordering;
+
+use anyhow::{anyhow, Error, Result};
+use aoc_runner_derive::*;
+
+/// A packet, made up of either a single integer or a list of packets. Equality follows the
+/// packet ordering, so `3` and `[3]` are considered equal.
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Int(u32),
+    List(Vec<Packet>),
+}
+
+/// Packets are ordered by comparing integers numerically and lists element-by-element, with the
+/// shorter list coming first if all elements are equal. When an integer is compared against a
+/// list, the integer is first promoted to a list containing only that integer.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day13::*;
+/// // Integers compare numerically.
+/// assert!(Packet::Int(3) < Packet::Int(5));
+/// assert!(Packet::Int(5) == Packet::Int(5));
+///
+/// // Lists compare element-by-element, then by length.
+/// let short = Packet::List(vec![Packet::Int(7), Packet::Int(7)]);
+/// let long = Packet::List(vec![Packet::Int(7), Packet::Int(7), Packet::Int(7)]);
+/// assert!(short < long);
+/// let high = Packet::List(vec![Packet::Int(9)]);
+/// assert!(long < high);
+/// assert!(Packet::List(vec![]) < Packet::List(vec![Packet::List(vec![])]));
+///
+/// // Integers are promoted to single-element lists when compared against a list.
+/// let list = Packet::List(vec![Packet::Int(3), Packet::Int(4)]);
+/// assert!(Packet::Int(3) < list);
+/// assert!(list > Packet::Int(3));
+/// assert_eq!(Packet::Int(3).cmp(&Packet::List(vec![Packet::Int(3)])), std::cmp::Ordering::Equal);
+/// assert!(Packet::Int(4) > list);
+/// ```
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(l), Packet::Int(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            (Packet::Int(l), r) => Packet::List(vec![Packet::Int(*l)]).cmp(r),
+            (l, Packet::Int(r)) => l.cmp(&Packet::List(vec![Packet::Int(*r)])),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+impl std::fmt::Display for Packet {
+    /// Format a packet the way it's written in the input.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day13::*;
+    /// for given in ["[1,[2,[3,[4,[5,6,7]]]],8,9]", "[[[]]]", "[]", "7"] {
+    ///     let packet = given.parse::<Packet>().unwrap();
+    ///     assert_eq!(packet.to_string(), given);
+    /// }
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Packet::Int(n) => write!(f, "{n}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// A single token of a packet.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Token {
+    LBracket,
+    RBracket,
+    Comma,
+    Num(u32),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::LBracket => write!(f, "`[`"),
+            Token::RBracket => write!(f, "`]`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Num(n) => write!(f, "`{n}`"),
+        }
+    }
+}
+
+/// Split a packet into tokens, each along with the 1-based column it starts at. Columns count
+/// bytes rather than chars, which only differ for characters that are already invalid.
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let token = match c {
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            ',' => Token::Comma,
+            '0'..='9' => {
+                let mut end = i + 1;
+                while let Some((j, '0'..='9')) = chars.peek() {
+                    end = j + 1;
+                    chars.next();
+                }
+                let n = s[i..end].parse().map_err(|e| anyhow!("{e} at column {}", i + 1))?;
+                Token::Num(n)
+            }
+            c => return Err(anyhow!("unexpected char {c:?} at column {}", i + 1)),
+        };
+        tokens.push((i + 1, token));
+    }
+    Ok(tokens)
+}
+
+/// Parse a single packet from the tokens starting at `pos`, advancing `pos` past the end of the
+/// packet. `end` is the column just past the last token, for reporting a packet cut short.
+fn parse_tokens(tokens: &[(usize, Token)], pos: &mut usize, end: usize) -> Result<Packet> {
+    match tokens.get(*pos) {
+        Some((_, Token::Num(n))) => {
+            *pos += 1;
+            Ok(Packet::Int(*n))
+        }
+        Some((open, Token::LBracket)) => {
+            *pos += 1;
+            let mut items = vec![];
+            if let Some((_, Token::RBracket)) = tokens.get(*pos) {
+                *pos += 1;
+                return Ok(Packet::List(items));
+            }
+            loop {
+                items.push(parse_tokens(tokens, pos, end)?);
+                match tokens.get(*pos) {
+                    Some((_, Token::Comma)) => *pos += 1,
+                    Some((_, Token::RBracket)) => {
+                        *pos += 1;
+                        return Ok(Packet::List(items));
+                    }
+                    Some((column, token)) => {
+                        return Err(anyhow!(
+                            "expected `,` or `]` at column {column}, found {token}"
+                        ))
+                    }
+                    None => return Err(anyhow!("unclosed `[` from column {open}")),
+                }
+            }
+        }
+        Some((column, token)) => {
+            Err(anyhow!("expected a packet at column {column}, found {token}"))
+        }
+        None => Err(anyhow!("expected a packet at column {end}, found the end of the line")),
+    }
+}
+
+impl std::str::FromStr for Packet {
+    type Err = Error;
+
+    /// Parse a packet from a nested, bracketed list of integers, such as `[1,[2,3],4]`. Malformed
+    /// packets give an error with the column where parsing went wrong, as a 1-based byte offset
+    /// into the packet once surrounding whitespace is trimmed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day13::*;
+    /// let error = |s: &str| s.parse::<Packet>().unwrap_err().to_string();
+    /// assert_eq!(error("[1,[2]"), "unclosed `[` from column 1");
+    /// assert_eq!(error("[1,2]]"), "unexpected `]` at column 6 after the packet");
+    /// assert_eq!(error("[1,]"), "expected a packet at column 4, found `]`");
+    /// assert_eq!(error("[1 2]"), "unexpected char ' ' at column 3");
+    /// assert_eq!(error("[[1]2]"), "expected `,` or `]` at column 5, found `2`");
+    /// assert_eq!(error(""), "expected a packet at column 1, found the end of the line");
+    /// assert_eq!(error("[1,99999999999]"), "number too large to fit in target type at column 4");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let tokens = tokenize(s)?;
+        let mut pos = 0;
+        let packet = parse_tokens(&tokens, &mut pos, s.len() + 1)?;
+        if let Some((column, token)) = tokens.get(pos) {
+            return Err(anyhow!("unexpected {token} at column {column} after the packet"));
+        }
+        Ok(packet)
+    }
+}
+
+/// Given an input in the form of pairs of packets separated by blank lines, generate a Vec of
+/// packet pairs.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day13::*;
+/// let pairs = input_generator("[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[]]\n[3]").unwrap();
+/// assert_eq!(pairs, vec![
+///     (
+///         Packet::List(vec![1, 1, 3, 1, 1].into_iter().map(Packet::Int).collect()),
+///         Packet::List(vec![1, 1, 5, 1, 1].into_iter().map(Packet::Int).collect()),
+///     ),
+///     (
+///         Packet::List(vec![Packet::List(vec![])]),
+///         Packet::List(vec![Packet::Int(3)]),
+///     ),
+/// ]);
+///
+/// let error = input_generator("[1]\n[2]\n\n[3]\n[4").unwrap_err();
+/// assert_eq!(error.to_string(), "line 5: unclosed `[` from column 1");
+/// let error = input_generator("[1]\n[2]\n\n[3]").unwrap_err();
+/// assert_eq!(error.to_string(), "line 5: missing right packet");
+/// ```
+#[aoc_generator(day13)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 13, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Vec<(Packet, Packet)>> {
+    input
+        .split("\n\n")
+        .enumerate()
+        .map(|(i, pair)| {
+            let mut lines = pair.lines();
+            let mut packet = |line: usize, side: &str| -> Result<Packet> {
+                let line = 3 * i + line;
+                let packet =
+                    lines.next().ok_or_else(|| anyhow!("line {line}: missing {side} packet"))?;
+                packet.parse().map_err(|e| anyhow!("line {line}: {e}"))
+            };
+            Ok((packet(1, "left")?, packet(2, "right")?))
+        })
+        .collect()
+}
+
+/// Sum the 1-based indices of all pairs that are already in the right order.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day13::*;
+/// let given_packets = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n\
+///     [[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n\
+///     [1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
+/// let pairs = input_generator(given_packets).unwrap();
+/// assert_eq!(ordered_pair_indices(&pairs), 13);
+/// ```
+pub fn ordered_pair_indices(pairs: &[(Packet, Packet)]) -> usize {
+    pairs.iter().enumerate().filter(|(_, (l, r))| l <= r).map(|(i, _)| i + 1).sum()
+}
+
+/// Insert the `[[2]]` and `[[6]]` divider packets, sort all packets, and multiply the 1-based
+/// positions of the dividers.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day13::*;
+/// let given_packets = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n\
+///     [[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n\
+///     [1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
+/// let pairs = input_generator(given_packets).unwrap();
+/// assert_eq!(decoder_key(&pairs), 140);
+/// ```
+pub fn decoder_key(pairs: &[(Packet, Packet)]) -> usize {
+    let dividers = [
+        Packet::List(vec![Packet::List(vec![Packet::Int(2)])]),
+        Packet::List(vec![Packet::List(vec![Packet::Int(6)])]),
+    ];
+    let mut packets = pairs
+        .iter()
+        .flat_map(|(l, r)| [l.clone(), r.clone()])
+        .chain(dividers.iter().cloned())
+        .collect::<Vec<_>>();
+    packets.sort();
+    dividers.iter().filter_map(|d| packets.iter().position(|p| p == d)).map(|i| i + 1).product()
+}
+
+#[doc(hidden)]
+#[aoc(day13, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 13, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[(Packet, Packet)]) -> String {
+    ordered_pair_indices(input).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day13, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 13, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[(Packet, Packet)]) -> String {
+    decoder_key(input).to_string()
+}
+
+solution! {
+    day: 13,
+    try_generator: input_generator,
+    example: "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n\
+        [[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n\
+        [1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]",
+    solve_part1: "13",
+    solve_part2: "140",
+}
@@ -0,0 +1,203 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::point::Point;
+
+/// Parse a single `x,y` coordinate.
+fn parse_point(s: &str) -> Result<Point> {
+    let (x, y) =
+        s.trim().split_once(',').ok_or_else(|| anyhow!("Was given an invalid point: {s}"))?;
+    Ok(Point::new(x.parse()?, y.parse()?))
+}
+
+/// Given an input in the form of rock paths, where each line is a series of points joined by
+/// `->`, generate the set of all points occupied by rock.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day14::*;
+/// # use aoc::point::Point;
+/// let rocks = input_generator("498,4 -> 498,6 -> 496,6").unwrap();
+/// assert_eq!(rocks.len(), 5);
+/// assert!(rocks.contains(&Point::new(498, 5)));
+/// assert!(rocks.contains(&Point::new(497, 6)));
+/// ```
+#[aoc_generator(day14)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 14, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<HashSet<Point>> {
+    let mut rocks = HashSet::new();
+    for line in input.lines() {
+        let points = line.split("->").map(parse_point).collect::<Result<Vec<_>>>()?;
+        for pair in points.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            for x in from.x.min(to.x)..=from.x.max(to.x) {
+                for y in from.y.min(to.y)..=from.y.max(to.y) {
+                    rocks.insert(Point::new(x, y));
+                }
+            }
+        }
+    }
+    Ok(rocks)
+}
+
+/// Drop sand from the source until either a unit of sand falls into the abyss below all blocked
+/// points (when `floor` is None) or the source itself is blocked (when `floor` is the height of
+/// an infinite floor). Sand falls down if possible, otherwise down-left, otherwise down-right,
+/// and otherwise settles. Returns the number of settled units of sand.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day14::*;
+/// # use aoc::point::Point;
+/// let given_rocks = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
+/// let rocks = input_generator(given_rocks).unwrap();
+/// let source = Point::new(500, 0);
+/// assert_eq!(drop_sand(&mut rocks.clone(), source, None), 24);
+///
+/// let floor = rocks.iter().map(|p| p.y).max().unwrap() + 2;
+/// assert_eq!(drop_sand(&mut rocks.clone(), source, Some(floor)), 93);
+/// ```
+pub fn drop_sand(blocked: &mut HashSet<Point>, source: Point, floor: Option<i64>) -> usize {
+    drop_sand_with(blocked, source, floor, |_, _| {})
+}
+
+/// Drop sand from the source as in [`drop_sand`], calling `on_settle` with the blocked points and
+/// the number of settled units of sand after each unit settles.
+pub fn drop_sand_with(
+    blocked: &mut HashSet<Point>,
+    source: Point,
+    floor: Option<i64>,
+    mut on_settle: impl FnMut(&HashSet<Point>, usize),
+) -> usize {
+    let abyss = blocked.iter().map(|p| p.y).max().unwrap_or(source.y);
+    let mut settled = 0;
+    while !blocked.contains(&source) {
+        let mut sand = source;
+        loop {
+            if floor.is_none() && sand.y > abyss {
+                return settled;
+            }
+            let next = [0, -1, 1]
+                .map(|dx| Point::new(sand.x + dx, sand.y + 1))
+                .into_iter()
+                .find(|p| !blocked.contains(p) && Some(p.y) != floor);
+            match next {
+                Some(p) => sand = p,
+                None => break,
+            }
+        }
+        blocked.insert(sand);
+        settled += 1;
+        on_settle(blocked, settled);
+    }
+    settled
+}
+
+/// Drop sand from the source as in [`drop_sand`], returning the number of settled units of sand
+/// after each unit settles.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day14::*;
+/// # use aoc::point::Point;
+/// let given_rocks = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9";
+/// let rocks = input_generator(given_rocks).unwrap();
+/// let trace = drop_sand_trace(&mut rocks.clone(), Point::new(500, 0), None);
+/// assert_eq!(trace.len(), 24);
+/// assert_eq!(trace.last(), Some(&24));
+/// assert!(trace.windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn drop_sand_trace(
+    blocked: &mut HashSet<Point>,
+    source: Point,
+    floor: Option<i64>,
+) -> Vec<usize> {
+    let mut trace = vec![];
+    drop_sand_with(blocked, source, floor, |_, settled| trace.push(settled));
+    trace
+}
+
+/// Drop sand from the source as in [`drop_sand`], returning the set of points where sand has
+/// settled, excluding the rocks themselves.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day14::*;
+/// # use aoc::point::Point;
+/// let rocks = input_generator("10,2 -> 10,4 -> 14,4 -> 14,2").unwrap();
+/// let sand = settle_sand(&rocks, Point::new(12, 0), None);
+/// assert_eq!(sand.len(), 10);
+/// assert!(sand.contains(&Point::new(12, 0)));
+/// assert!(sand.contains(&Point::new(11, 1)));
+/// assert!(!sand.contains(&Point::new(10, 1)));
+/// assert_eq!(render(&rocks, &sand), "..o..\n.ooo.\n#ooo#\n#ooo#\n#####");
+/// ```
+pub fn settle_sand(rocks: &HashSet<Point>, source: Point, floor: Option<i64>) -> HashSet<Point> {
+    let mut blocked = rocks.clone();
+    drop_sand(&mut blocked, source, floor);
+    blocked.difference(rocks).copied().collect()
+}
+
+/// Render rocks as `#`, sand as `o`, and air as `.`, within the bounding box of both.
+pub fn render(rocks: &HashSet<Point>, sand: &HashSet<Point>) -> String {
+    let all = || rocks.iter().chain(sand.iter());
+    let (Some(min_x), Some(max_x)) = (all().map(|p| p.x).min(), all().map(|p| p.x).max()) else {
+        return String::new();
+    };
+    let (min_y, max_y) = (all().map(|p| p.y).min().unwrap(), all().map(|p| p.y).max().unwrap());
+    (min_y..=max_y)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| {
+                    let p = Point::new(x, y);
+                    if rocks.contains(&p) {
+                        '#'
+                    } else if sand.contains(&p) {
+                        'o'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[doc(hidden)]
+#[aoc(day14, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 14, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &HashSet<Point>) -> String {
+    drop_sand(&mut input.clone(), Point { x: 500, y: 0 }, None).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day14, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 14, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &HashSet<Point>) -> String {
+    let floor = input.iter().map(|p| p.y).max().unwrap_or(0) + 2;
+    drop_sand(&mut input.clone(), Point { x: 500, y: 0 }, Some(floor)).to_string()
+}
+
+solution! {
+    day: 14,
+    try_generator: input_generator,
+    example: "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9",
+    solve_part1: "24",
+    solve_part2: "93",
+}
@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::intervals;
+use crate::parse::extract_ints;
+use crate::point::Point;
+
+/// A sensor and the closest beacon it has detected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sensor {
+    pub position: Point,
+    pub beacon: Point,
+}
+
+impl Sensor {
+    /// The Manhattan distance covered by the sensor, within which no other beacon may exist.
+    pub fn radius(&self) -> i64 {
+        self.position.manhattan(&self.beacon)
+    }
+
+    /// The inclusive range of x positions covered by the sensor on the given row, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day15::*;
+    /// # use aoc::point::Point;
+    /// let sensor = Sensor { position: Point::new(8, 7), beacon: Point::new(2, 10) };
+    /// assert_eq!(sensor.coverage(10), Some((2, 14)));
+    /// assert_eq!(sensor.coverage(16), Some((8, 8)));
+    /// assert_eq!(sensor.coverage(17), None);
+    /// ```
+    pub fn coverage(&self, row: i64) -> Option<(i64, i64)> {
+        let remaining = self.radius() - (self.position.y - row).abs();
+        (remaining >= 0).then_some((self.position.x - remaining, self.position.x + remaining))
+    }
+}
+
+/// Given an input in the form of sensor reports, such as
+/// `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`, generate a Vec of Sensors.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day15::*;
+/// # use aoc::point::Point;
+/// let sensors = input_generator("Sensor at x=2, y=18: closest beacon is at x=-2, y=15").unwrap();
+/// assert_eq!(sensors, vec![Sensor { position: Point::new(2, 18), beacon: Point::new(-2, 15) }]);
+/// ```
+#[aoc_generator(day15)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 15, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Vec<Sensor>> {
+    input
+        .lines()
+        .map(|line| match extract_ints(line)[..] {
+            [sx, sy, bx, by] => {
+                Ok(Sensor { position: Point::new(sx, sy), beacon: Point::new(bx, by) })
+            }
+            _ => Err(anyhow!("Was given an invalid sensor: {line}")),
+        })
+        .collect()
+}
+
+/// Count the positions on a row where a beacon cannot be present.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day15::*;
+/// let given_sensors = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// Sensor at x=9, y=16: closest beacon is at x=10, y=16
+/// Sensor at x=13, y=2: closest beacon is at x=15, y=3
+/// Sensor at x=12, y=14: closest beacon is at x=10, y=16
+/// Sensor at x=10, y=20: closest beacon is at x=10, y=16
+/// Sensor at x=14, y=17: closest beacon is at x=10, y=16
+/// Sensor at x=8, y=7: closest beacon is at x=2, y=10
+/// Sensor at x=2, y=0: closest beacon is at x=2, y=10
+/// Sensor at x=0, y=11: closest beacon is at x=2, y=10
+/// Sensor at x=20, y=14: closest beacon is at x=25, y=17
+/// Sensor at x=17, y=20: closest beacon is at x=21, y=22
+/// Sensor at x=16, y=7: closest beacon is at x=15, y=3
+/// Sensor at x=14, y=3: closest beacon is at x=15, y=3
+/// Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+/// let sensors = input_generator(given_sensors).unwrap();
+/// for (row, expected) in [(9, 25), (10, 26), (11, 28)] {
+///     assert_eq!(excluded_on_row(&sensors, row), expected);
+/// }
+/// ```
+pub fn excluded_on_row(sensors: &[Sensor], row: i64) -> usize {
+    let ranges = sensors.iter().filter_map(|s| s.coverage(row)).collect::<Vec<_>>();
+    let intervals = intervals::merge(&ranges, true);
+    let covered: i64 = intervals.iter().map(|(start, end)| end - start + 1).sum();
+    let beacons = sensors
+        .iter()
+        .map(|s| s.beacon)
+        .filter(|b| {
+            b.y == row && intervals.iter().any(|(start, end)| (*start..=*end).contains(&b.x))
+        })
+        .collect::<HashSet<_>>();
+    covered as usize - beacons.len()
+}
+
+/// Find the only position within `0..=bound` on both axes that is not covered by any sensor, by
+/// merging the coverage intervals of each row and looking for the first gap.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day15::*;
+/// # use aoc::point::Point;
+/// let given_sensors = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// Sensor at x=9, y=16: closest beacon is at x=10, y=16
+/// Sensor at x=13, y=2: closest beacon is at x=15, y=3
+/// Sensor at x=12, y=14: closest beacon is at x=10, y=16
+/// Sensor at x=10, y=20: closest beacon is at x=10, y=16
+/// Sensor at x=14, y=17: closest beacon is at x=10, y=16
+/// Sensor at x=8, y=7: closest beacon is at x=2, y=10
+/// Sensor at x=2, y=0: closest beacon is at x=2, y=10
+/// Sensor at x=0, y=11: closest beacon is at x=2, y=10
+/// Sensor at x=20, y=14: closest beacon is at x=25, y=17
+/// Sensor at x=17, y=20: closest beacon is at x=21, y=22
+/// Sensor at x=16, y=7: closest beacon is at x=15, y=3
+/// Sensor at x=14, y=3: closest beacon is at x=15, y=3
+/// Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+/// let sensors = input_generator(given_sensors).unwrap();
+/// let distress = find_distress(&sensors, 20).unwrap();
+/// assert_eq!(distress, Point::new(14, 11));
+/// assert_eq!(tuning_frequency(&distress), 56000011);
+/// ```
+pub fn find_distress(sensors: &[Sensor], bound: i64) -> Option<Point> {
+    (0..=bound).find_map(|row| {
+        let ranges = sensors
+            .iter()
+            .filter_map(|s| s.coverage(row))
+            .filter(|(start, end)| *end >= 0 && *start <= bound)
+            .collect::<Vec<_>>();
+        match intervals::merge(&ranges, true)[..] {
+            [] => Some(Point::new(0, row)),
+            [(start, _), ..] if start > 0 => Some(Point::new(0, row)),
+            [(_, end), ..] if end < bound => Some(Point::new(end + 1, row)),
+            _ => None,
+        }
+    })
+}
+
+/// Find the same position as [`find_distress`] without scanning every row. Rotating by 45°, with
+/// `a = y - x` and `b = x + y`, turns each sensor's diamond into an axis-aligned square. Since
+/// only one position is uncovered, it usually sits in a gap one cell wide between two sensors
+/// whose edges are exactly `2r + 2` apart in total, so the lines just outside of those sensors
+/// meet at the position. A position on the edge of the search area may have no sensor beyond it,
+/// so where the line just outside of each sensor meets an edge is also a candidate, as are the
+/// corners. Each candidate is checked against every sensor, and if none of them are uncovered,
+/// the rows are scanned with [`find_distress`] instead so that both always agree.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day15::*;
+/// # use aoc::point::Point;
+/// let given_sensors = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// Sensor at x=9, y=16: closest beacon is at x=10, y=16
+/// Sensor at x=13, y=2: closest beacon is at x=15, y=3
+/// Sensor at x=12, y=14: closest beacon is at x=10, y=16
+/// Sensor at x=10, y=20: closest beacon is at x=10, y=16
+/// Sensor at x=14, y=17: closest beacon is at x=10, y=16
+/// Sensor at x=8, y=7: closest beacon is at x=2, y=10
+/// Sensor at x=2, y=0: closest beacon is at x=2, y=10
+/// Sensor at x=0, y=11: closest beacon is at x=2, y=10
+/// Sensor at x=20, y=14: closest beacon is at x=25, y=17
+/// Sensor at x=17, y=20: closest beacon is at x=21, y=22
+/// Sensor at x=16, y=7: closest beacon is at x=15, y=3
+/// Sensor at x=14, y=3: closest beacon is at x=15, y=3
+/// Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+/// let sensors = input_generator(given_sensors).unwrap();
+/// let distress = find_distress_rotated(&sensors, 20).unwrap();
+/// assert_eq!(distress, Point::new(14, 11));
+/// assert_eq!(find_distress(&sensors, 20), Some(distress));
+///
+/// // A gap in the corner of the search area.
+/// let sensor = Sensor { position: Point::new(4, 4), beacon: Point::new(4, 11) };
+/// assert_eq!(find_distress_rotated(&[sensor.clone()], 4), Some(Point::new(0, 0)));
+/// assert_eq!(find_distress(&[sensor], 4), Some(Point::new(0, 0)));
+///
+/// // A gap along the edge of the search area, with no sensors beyond it.
+/// let sensors = [(1, 0), (1, 4), (4, 2)]
+///     .map(|(x, y)| Sensor { position: Point::new(x, y), beacon: Point::new(1, 2) });
+/// assert_eq!(find_distress_rotated(&sensors, 4), Some(Point::new(0, 2)));
+/// assert_eq!(find_distress(&sensors, 4), Some(Point::new(0, 2)));
+/// ```
+pub fn find_distress_rotated(sensors: &[Sensor], bound: i64) -> Option<Point> {
+    let mut ascending = HashSet::new();
+    let mut descending = HashSet::new();
+    for first in sensors {
+        for second in sensors {
+            let (p, q) = (first.position, second.position);
+            let (r, s) = (first.radius(), second.radius());
+            if (q.y - q.x) - (p.y - p.x) == r + s + 2 {
+                ascending.insert(p.y - p.x + r + 1);
+            }
+            if (q.x + q.y) - (p.x + p.y) == r + s + 2 {
+                descending.insert(p.x + p.y + r + 1);
+            }
+        }
+    }
+    let crossings = ascending.iter().flat_map(|a| {
+        descending
+            .iter()
+            .filter(move |b| (*b - a) % 2 == 0)
+            .map(move |b| Point::new((b - a) / 2, (b + a) / 2))
+    });
+    let edges = sensors.iter().flat_map(|sensor| {
+        let (p, r) = (sensor.position, sensor.radius());
+        let (a, b) = (p.y - p.x, p.x + p.y);
+        [a - r - 1, a + r + 1, b - r - 1, b + r + 1].into_iter().enumerate().flat_map(|(i, c)| {
+            if i < 2 {
+                [(0, c), (bound, c + bound), (-c, 0), (bound - c, bound)]
+            } else {
+                [(0, c), (bound, c - bound), (c, 0), (c - bound, bound)]
+            }
+        })
+    });
+    let corners = [(0, 0), (bound, 0), (0, bound), (bound, bound)];
+    let edges = edges.chain(corners).map(|(x, y)| Point::new(x, y));
+    crossings
+        .chain(edges)
+        .find(|p| {
+            (0..=bound).contains(&p.x)
+                && (0..=bound).contains(&p.y)
+                && sensors.iter().all(|s| s.position.manhattan(p) > s.radius())
+        })
+        .or_else(|| find_distress(sensors, bound))
+}
+
+/// Get the tuning frequency of a distress beacon.
+pub fn tuning_frequency(point: &Point) -> i64 {
+    point.x * 4000000 + point.y
+}
+
+#[doc(hidden)]
+#[aoc(day15, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 15, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[Sensor]) -> String {
+    excluded_on_row(input, 2000000).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day15, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 15, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[Sensor]) -> String {
+    find_distress_rotated(input, 4000000)
+        .map(|p| tuning_frequency(&p))
+        .expect("no distress beacon found")
+        .to_string()
+}
+
+// The first part always looks at row 2000000, which the example never reaches, so only
+// `excluded_on_row` is checked against the example's row 10. Searching up to 4000000 for the
+// second part still finds the example's only gap.
+solution! {
+    day: 15,
+    try_generator: input_generator,
+    example: "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3",
+    solve_part2: "56000011",
+}
@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::pathfinding::floyd_warshall;
+
+/// A valve, the rate at which it releases pressure once opened, and the valves it has tunnels to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valve {
+    pub name: String,
+    pub flow: u32,
+    pub tunnels: Vec<String>,
+}
+
+impl std::str::FromStr for Valve {
+    type Err = anyhow::Error;
+
+    /// Parse a valve from a line such as
+    /// `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        let name = words.get(1).ok_or_else(|| anyhow!("Was given an invalid valve: {s}"))?;
+        let flow = words
+            .get(4)
+            .and_then(|w| w.strip_prefix("rate="))
+            .and_then(|w| w.strip_suffix(';'))
+            .ok_or_else(|| anyhow!("Was given an invalid flow rate: {s}"))?
+            .parse()?;
+        let tunnels = words.iter().skip(9).map(|w| w.trim_end_matches(',').to_string()).collect();
+        Ok(Valve { name: name.to_string(), flow, tunnels })
+    }
+}
+
+/// Hashes the packed search states of [`Network::max_pressure`] with a single multiply, as the
+/// default hasher is built to resist collisions from untrusted keys and spends most of the search
+/// doing so.
+#[derive(Default)]
+struct StateHasher(u64);
+
+impl Hasher for StateHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_u64(*byte as u64);
+        }
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517cc1b727220a95);
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write_u64(n as u64);
+        self.write_u64((n >> 64) as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The best pressure found from each search state, keyed by the opened valves, the time left,
+/// and the current valve packed into a single number.
+type Memo = HashMap<u128, u32, BuildHasherDefault<StateHasher>>;
+
+/// The valves worth opening, along with the starting valve, and the shortest distances between
+/// each of them. Valve indices are stable between `flows` and `distances`, and the starting
+/// valve is always the last index.
+#[derive(Clone, Debug)]
+pub struct Network {
+    pub flows: Vec<u32>,
+    pub distances: Vec<Vec<u32>>,
+    pub start: usize,
+}
+
+impl Network {
+    /// Build a network from a list of valves, computing the shortest path between each pair of
+    /// valves and then dropping all valves with no flow, except for the starting valve `AA`.
+    pub fn new(valves: &[Valve]) -> Result<Network> {
+        let index: HashMap<&str, usize> =
+            valves.iter().enumerate().map(|(i, v)| (v.name.as_str(), i)).collect();
+        let mut edges = vec![];
+        for (i, valve) in valves.iter().enumerate() {
+            for tunnel in &valve.tunnels {
+                let j =
+                    index.get(tunnel.as_str()).ok_or_else(|| anyhow!("Unknown valve: {tunnel}"))?;
+                edges.push((i, *j, 1));
+            }
+        }
+        let n = valves.len();
+        let all = floyd_warshall(n, &edges);
+        let start = *index.get("AA").ok_or_else(|| anyhow!("Missing starting valve AA"))?;
+        let mut kept = (0..n).filter(|i| valves[*i].flow > 0).collect::<Vec<_>>();
+        if kept.len() >= 64 {
+            return Err(anyhow!("Too many valves with flow to track as a bitmask"));
+        }
+        kept.push(start);
+        Ok(Network {
+            flows: kept.iter().map(|i| valves[*i].flow).collect(),
+            distances: kept.iter().map(|i| kept.iter().map(|j| all[*i][*j]).collect()).collect(),
+            start: kept.len() - 1,
+        })
+    }
+
+    /// Search every order of opening valves reachable within the time limit, tracking opened
+    /// valves as a bitmask, and return the most pressure that can be released.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day16::*;
+    /// let given_valves = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    /// Valve BB has flow rate=13; tunnels lead to valves CC, AA
+    /// Valve CC has flow rate=2; tunnels lead to valves DD, BB
+    /// Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+    /// Valve EE has flow rate=3; tunnels lead to valves FF, DD
+    /// Valve FF has flow rate=0; tunnels lead to valves EE, GG
+    /// Valve GG has flow rate=0; tunnels lead to valves FF, HH
+    /// Valve HH has flow rate=22; tunnel leads to valve GG
+    /// Valve II has flow rate=0; tunnels lead to valves AA, JJ
+    /// Valve JJ has flow rate=21; tunnel leads to valve II";
+    /// let network = input_generator(given_valves).unwrap();
+    /// assert_eq!(network.max_pressure(30), 1651);
+    /// ```
+    pub fn max_pressure(&self, minutes: u32) -> u32 {
+        // A puzzle-sized network reaches a couple hundred thousand states; skip the small resizes.
+        let mut memo = Memo::with_capacity_and_hasher(1 << 16, Default::default());
+        self.best_from(self.start, minutes, 0, &mut memo)
+    }
+
+    /// The most pressure that can be released from opening more valves, starting at `current`
+    /// with the valves in `opened` already open. Different orders of opening the same valves can
+    /// end up at the same valve with the same time left, so each result is remembered.
+    fn best_from(&self, current: usize, time_left: u32, opened: u64, memo: &mut Memo) -> u32 {
+        let key = (opened as u128) << 64 | (time_left as u128) << 32 | current as u128;
+        if let Some(best) = memo.get(&key) {
+            return *best;
+        }
+        let mut best = 0;
+        for next in 0..self.start {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+            let cost = self.distances[current][next] + 1;
+            if cost >= time_left {
+                continue;
+            }
+            let remaining = time_left - cost;
+            let released = remaining * self.flows[next];
+            best = best.max(released + self.best_from(next, remaining, opened | (1 << next), memo));
+        }
+        memo.insert(key, best);
+        best
+    }
+
+    /// Search every order of opening valves reachable within the time limit, and return the most
+    /// pressure that can be released for each set of opened valves, keyed by bitmask.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day16::*;
+    /// let given_valves = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+    /// Valve BB has flow rate=13; tunnels lead to valves CC, AA
+    /// Valve CC has flow rate=2; tunnels lead to valves DD, BB
+    /// Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+    /// Valve EE has flow rate=3; tunnels lead to valves FF, DD
+    /// Valve FF has flow rate=0; tunnels lead to valves EE, GG
+    /// Valve GG has flow rate=0; tunnels lead to valves FF, HH
+    /// Valve HH has flow rate=22; tunnel leads to valve GG
+    /// Valve II has flow rate=0; tunnels lead to valves AA, JJ
+    /// Valve JJ has flow rate=21; tunnel leads to valve II";
+    /// let network = input_generator(given_valves).unwrap();
+    /// let scores = network.subset_pressures(30);
+    /// assert_eq!(scores.values().max(), Some(&1651));
+    /// assert_eq!(scores[&0], 0);
+    ///
+    /// let scores = network.subset_pressures(26);
+    /// assert_eq!(best_pair(&scores), 1707);
+    /// ```
+    pub fn subset_pressures(&self, minutes: u32) -> HashMap<u64, u32> {
+        let mut scores = HashMap::new();
+        self.search(self.start, minutes, 0, 0, &mut |opened, released| {
+            let best = scores.entry(opened).or_insert(0);
+            *best = (*best).max(released);
+        });
+        scores
+    }
+
+    fn search(
+        &self,
+        current: usize,
+        time_left: u32,
+        opened: u64,
+        released: u32,
+        record: &mut impl FnMut(u64, u32),
+    ) {
+        record(opened, released);
+        for next in 0..self.start {
+            if opened & (1 << next) != 0 {
+                continue;
+            }
+            // Walking to the valve and opening it takes one minute per tunnel plus one minute.
+            let cost = self.distances[current][next] + 1;
+            if cost >= time_left {
+                continue;
+            }
+            let remaining = time_left - cost;
+            let released = released + remaining * self.flows[next];
+            self.search(next, remaining, opened | (1 << next), released, record);
+        }
+    }
+}
+
+/// Given the best pressure released for each set of opened valves, find the most pressure that
+/// can be released by two agents opening disjoint sets of valves.
+pub fn best_pair(scores: &HashMap<u64, u32>) -> u32 {
+    let mut scores = scores.iter().collect::<Vec<_>>();
+    scores.sort_by(|a, b| b.1.cmp(a.1));
+    let mut best = 0;
+    for (i, (mask_a, score_a)) in scores.iter().enumerate() {
+        // Scores are sorted descending, so no later pairing can beat the best found so far.
+        if **score_a * 2 < best {
+            break;
+        }
+        for (mask_b, score_b) in &scores[i..] {
+            if *mask_a & *mask_b == 0 {
+                best = best.max(*score_a + *score_b);
+                break;
+            }
+        }
+    }
+    best
+}
+
+/// Given an input in the form of valve descriptions, generate a Network of valves.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day16::*;
+/// let given_valves = "Valve AA has flow rate=0; tunnels lead to valves BB
+/// Valve BB has flow rate=13; tunnels lead to valves CC, AA
+/// Valve CC has flow rate=0; tunnel leads to valve BB";
+/// let network = input_generator(given_valves).unwrap();
+/// assert_eq!(network.flows, vec![13, 0]);
+/// assert_eq!(network.distances, vec![vec![0, 1], vec![1, 0]]);
+/// assert_eq!(network.start, 1);
+/// ```
+#[aoc_generator(day16)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 16, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Network> {
+    let valves = input.lines().map(str::parse).collect::<Result<Vec<Valve>>>()?;
+    Network::new(&valves)
+}
+
+#[doc(hidden)]
+#[aoc(day16, part1)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 16, part = 1)))]
+pub fn solve_part1(input: &Network) -> String {
+    input.max_pressure(30).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day16, part2)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 16, part = 2)))]
+pub fn solve_part2(input: &Network) -> String {
+    best_pair(&input.subset_pressures(26)).to_string()
+}
+
+solution! {
+    day: 16,
+    try_generator: input_generator,
+    example: "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II",
+    solve_part1: "1651",
+    solve_part2: "1707",
+}
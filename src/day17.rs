@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+/// The five rock shapes, in the order they fall, as rows from the bottom up. Each bit of a row is
+/// a column of the 7-wide chamber, with the most significant of the seven bits being the
+/// leftmost column. Rocks start with their left edge two units from the left wall.
+const ROCKS: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+const LEFT_WALL: u8 = 0b1000000;
+const RIGHT_WALL: u8 = 0b0000001;
+
+/// A chamber of settled rocks, stored as one bitmask per row from the floor up, along with the
+/// position in the jet pattern and the rock sequence.
+#[derive(Clone, Debug)]
+pub struct Chamber {
+    rows: Vec<u8>,
+    jets: Vec<bool>,
+    jet: usize,
+    rock: usize,
+}
+
+impl Chamber {
+    /// Create an empty chamber from a jet pattern, where `<` pushes rocks left and `>` pushes
+    /// rocks right.
+    pub fn new(jets: &str) -> Result<Chamber> {
+        let jets = jets
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '<' => Ok(true),
+                '>' => Ok(false),
+                c => Err(anyhow!("Was given an invalid jet: {c}")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if jets.is_empty() {
+            return Err(anyhow!("Was given an empty jet pattern"));
+        }
+        Ok(Chamber { rows: vec![], jets, jet: 0, rock: 0 })
+    }
+
+    /// The height of the tower of settled rocks.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The depth of the topmost settled rock in each column, measured down from the top of the
+    /// tower. Together with the rock and jet positions, this identifies a repeating state.
+    fn profile(&self) -> [usize; 7] {
+        let mut profile = [self.rows.len(); 7];
+        for (column, depth) in profile.iter_mut().enumerate() {
+            let mask = LEFT_WALL >> column;
+            if let Some(found) = self.rows.iter().rev().position(|row| row & mask != 0) {
+                *depth = found;
+            }
+        }
+        profile
+    }
+
+    fn collides(&self, rock: &[u8], y: usize) -> bool {
+        rock.iter().enumerate().any(|(i, r)| self.rows.get(y + i).is_some_and(|row| row & r != 0))
+    }
+
+    /// Drop the next rock, pushing it by jets and letting it fall until it comes to rest.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day17::*;
+    /// let mut chamber = Chamber::new(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>").unwrap();
+    /// chamber.drop_rock();
+    /// assert_eq!(chamber.height(), 1);
+    /// chamber.drop_rock();
+    /// assert_eq!(chamber.height(), 4);
+    /// for _ in 2..2022 {
+    ///     chamber.drop_rock();
+    /// }
+    /// assert_eq!(chamber.height(), 3068);
+    /// ```
+    pub fn drop_rock(&mut self) {
+        let mut rock = ROCKS[self.rock].to_vec();
+        self.rock = (self.rock + 1) % ROCKS.len();
+        let mut y = self.rows.len() + 3;
+        loop {
+            let left = self.jets[self.jet];
+            self.jet = (self.jet + 1) % self.jets.len();
+            let pushed = if left {
+                if rock.iter().any(|r| r & LEFT_WALL != 0) {
+                    None
+                } else {
+                    Some(rock.iter().map(|r| r << 1).collect::<Vec<_>>())
+                }
+            } else if rock.iter().any(|r| r & RIGHT_WALL != 0) {
+                None
+            } else {
+                Some(rock.iter().map(|r| r >> 1).collect::<Vec<_>>())
+            };
+            if let Some(pushed) = pushed.filter(|p| !self.collides(p, y)) {
+                rock = pushed;
+            }
+            if y == 0 || self.collides(&rock, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+        for (i, r) in rock.iter().enumerate() {
+            if y + i >= self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + i] |= r;
+        }
+    }
+}
+
+/// Get the height of the tower after the given number of rocks have fallen.
+///
+/// # Panics
+///
+/// If the jet pattern is empty or contains anything other than `<` and `>`, the function will
+/// panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day17::*;
+/// let jets = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+/// assert_eq!(tower_height(jets, 2022), 3068);
+/// assert_eq!(tower_height(jets, 1000000000000), 1514285714288);
+/// ```
+pub fn tower_height(jets: &str, rocks: u64) -> u64 {
+    heights_at(jets, &[rocks])[0]
+}
+
+/// Get the height of the tower after each of the given numbers of rocks have fallen. Rocks are
+/// only simulated until the chamber reaches a state it has already seen, after which the height
+/// at any larger count is extrapolated from the height gained per cycle.
+///
+/// # Panics
+///
+/// If the jet pattern is empty or contains anything other than `<` and `>`, the function will
+/// panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day17::*;
+/// let jets = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+/// assert_eq!(heights_at(jets, &[2022, 1000000000000]), [3068, 1514285714288]);
+/// assert_eq!(heights_at(jets, &[0, 1, 2, 2022]), [0, 1, 4, 3068]);
+/// ```
+pub fn heights_at(jets: &str, counts: &[u64]) -> Vec<u64> {
+    let mut chamber = Chamber::new(jets).expect("invalid jet pattern");
+    let target = counts.iter().copied().max().unwrap_or(0);
+    // The height after each number of rocks, starting from the empty chamber.
+    let mut heights = vec![0];
+    let mut seen = HashMap::new();
+    let mut cycle = None;
+    while (heights.len() as u64) <= target {
+        chamber.drop_rock();
+        let dropped = heights.len() as u64;
+        heights.push(chamber.height() as u64);
+        let key = (chamber.rock, chamber.jet, chamber.profile());
+        if let Some(previous) = seen.insert(key, dropped) {
+            cycle = Some((previous, dropped - previous));
+            break;
+        }
+    }
+    counts
+        .iter()
+        .map(|&count| match cycle {
+            Some((start, length)) if count >= heights.len() as u64 => {
+                let gain = heights[(start + length) as usize] - heights[start as usize];
+                let (cycles, offset) = ((count - start) / length, (count - start) % length);
+                heights[(start + offset) as usize] + cycles * gain
+            }
+            _ => heights[count as usize],
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+#[aoc(day17, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 17, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &str) -> String {
+    tower_height(input, 2022).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day17, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 17, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &str) -> String {
+    tower_height(input, 1000000000000).to_string()
+}
+
+solution! {
+    day: 17,
+    generator: String::from,
+    example: ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>",
+    solve_part1: "3068",
+    solve_part2: "1514285714288",
+}
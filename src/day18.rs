@@ -0,0 +1,133 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::grid::{Grid3, NEIGHBORS6};
+use crate::parse::extract_ints;
+
+/// A single cube of lava, by its x, y, and z coordinates.
+pub type Cube = (i32, i32, i32);
+
+/// Get the six cubes sharing a face with the given cube.
+pub fn neighbors(cube: Cube) -> impl Iterator<Item = Cube> {
+    let (x, y, z) = cube;
+    NEIGHBORS6.into_iter().map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+}
+
+/// Given an input in the form of `x,y,z` coordinates across lines, generate a set of Cubes.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day18::*;
+/// let cubes = input_generator("1,1,1\n2,1,1").unwrap();
+/// assert_eq!(cubes, [(1, 1, 1), (2, 1, 1)].into());
+///
+/// let error = input_generator("1,1,1\n1,2").unwrap_err();
+/// assert_eq!(error.to_string(), "day18: expected 3 coords on line 2");
+/// ```
+#[aoc_generator(day18)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 18, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<HashSet<Cube>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match extract_ints(line)[..] {
+            [x, y, z] => Ok((x as i32, y as i32, z as i32)),
+            _ => Err(anyhow!("day18: expected 3 coords on line {}", i + 1)),
+        })
+        .collect()
+}
+
+/// Count the faces of all cubes that are not shared with another cube.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day18::*;
+/// assert_eq!(surface_area(&input_generator("1,1,1\n2,1,1").unwrap()), 10);
+///
+/// let given_cubes = "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n\
+///     3,2,5\n2,1,5\n2,3,5";
+/// assert_eq!(surface_area(&input_generator(given_cubes).unwrap()), 64);
+/// ```
+pub fn surface_area(cubes: &HashSet<Cube>) -> usize {
+    cubes.iter().flat_map(|c| neighbors(*c)).filter(|n| !cubes.contains(n)).count()
+}
+
+/// Count the faces of all cubes that are reachable from outside of the droplet, excluding any air
+/// pockets trapped inside. Air is flood-filled from a corner of the bounding box, which is grown
+/// by one in each direction so that the flood can reach around every side of the droplet.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day18::*;
+/// let given_cubes = "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n\
+///     3,2,5\n2,1,5\n2,3,5";
+/// assert_eq!(exterior_surface(&input_generator(given_cubes).unwrap()), 58);
+/// ```
+pub fn exterior_surface(cubes: &HashSet<Cube>) -> usize {
+    let Some(first) = cubes.iter().next() else {
+        return 0;
+    };
+    let (mut min, mut max) = (*first, *first);
+    for (x, y, z) in cubes {
+        min = (min.0.min(*x), min.1.min(*y), min.2.min(*z));
+        max = (max.0.max(*x), max.1.max(*y), max.2.max(*z));
+    }
+    let (min, max) = ((min.0 - 1, min.1 - 1, min.2 - 1), (max.0 + 1, max.1 + 1, max.2 + 1));
+    let mut lava = Grid3::new(min, max, false);
+    for cube in cubes {
+        lava.set(*cube, true);
+    }
+
+    let mut air = Grid3::new(min, max, false);
+    air.set(min, true);
+    let mut queue = VecDeque::from([min]);
+    let mut faces = 0;
+    while let Some(current) = queue.pop_front() {
+        for next in lava.neighbors6(current) {
+            if lava.get(next) == Some(&true) {
+                faces += 1;
+            } else if air.get(next) == Some(&false) {
+                air.set(next, true);
+                queue.push_back(next);
+            }
+        }
+    }
+    faces
+}
+
+#[doc(hidden)]
+#[aoc(day18, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 18, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &HashSet<Cube>) -> String {
+    surface_area(input).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day18, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 18, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &HashSet<Cube>) -> String {
+    exterior_surface(input).to_string()
+}
+
+solution! {
+    day: 18,
+    try_generator: input_generator,
+    example: "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n\
+        3,2,5\n2,1,5\n2,3,5",
+    solve_part1: "64",
+    solve_part2: "58",
+}
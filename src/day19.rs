@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::parse::extract_ints;
+
+/// The resources, in order, that robots can collect. Each resource has a robot that collects it.
+pub const ORE: usize = 0;
+pub const CLAY: usize = 1;
+pub const OBSIDIAN: usize = 2;
+pub const GEODE: usize = 3;
+
+/// A blueprint for a robot factory, holding the cost of each robot in each resource. Costs are
+/// indexed as `costs[robot][resource]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Blueprint {
+    pub id: u32,
+    pub costs: [[u32; 4]; 4],
+}
+
+/// Given an input in the form of blueprint descriptions, generate a Vec of Blueprints.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day19::*;
+/// let given_blueprint = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+///     Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
+/// let blueprints = input_generator(given_blueprint).unwrap();
+/// assert_eq!(blueprints, vec![Blueprint {
+///     id: 1,
+///     costs: [[4, 0, 0, 0], [2, 0, 0, 0], [3, 14, 0, 0], [2, 0, 7, 0]],
+/// }]);
+/// ```
+#[aoc_generator(day19)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 19, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Vec<Blueprint>> {
+    input
+        .lines()
+        .map(|line| match extract_ints(line)[..] {
+            [id, ore, clay, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] => {
+                Ok(Blueprint {
+                    id: id as u32,
+                    costs: [
+                        [ore as u32, 0, 0, 0],
+                        [clay as u32, 0, 0, 0],
+                        [obsidian_ore as u32, obsidian_clay as u32, 0, 0],
+                        [geode_ore as u32, 0, geode_obsidian as u32, 0],
+                    ],
+                })
+            }
+            _ => Err(anyhow!("Was given an invalid blueprint: {line}")),
+        })
+        .collect()
+}
+
+/// Search every order of building robots within the time limit, choosing which robot to build
+/// next and waiting until it can be afforded, and record the most geodes that can be opened.
+/// Waiting is only ever done on the way to a specific robot, so idle states are never explored
+/// on their own. Branches are pruned when they can't possibly beat the best result found so far.
+fn search(
+    bp: &Blueprint,
+    max_demand: &[u32; 4],
+    robots: [u32; 4],
+    resources: [u32; 4],
+    time_left: u32,
+    best: &mut u32,
+) {
+    *best = (*best).max(resources[GEODE] + robots[GEODE] * time_left);
+    // Even if a geode robot were built every remaining minute, this branch couldn't do better.
+    let optimistic =
+        resources[GEODE] + robots[GEODE] * time_left + time_left * time_left.saturating_sub(1) / 2;
+    if optimistic <= *best {
+        return;
+    }
+    // Try geode robots first, as finding a good result early prunes more branches.
+    for robot in [GEODE, OBSIDIAN, CLAY, ORE] {
+        // Only one robot can be built each minute, so there's no use in having more of a robot
+        // than could ever be spent on in the time remaining.
+        if robot != GEODE
+            && robots[robot] * time_left + resources[robot] >= max_demand[robot] * time_left
+        {
+            continue;
+        }
+        let cost = bp.costs[robot];
+        // Find how long until the robot can be afforded, if it ever can with the current robots.
+        let mut wait = 0;
+        let mut possible = true;
+        for resource in [ORE, CLAY, OBSIDIAN] {
+            if cost[resource] <= resources[resource] {
+                continue;
+            }
+            if robots[resource] == 0 {
+                possible = false;
+                break;
+            }
+            let missing = cost[resource] - resources[resource];
+            wait = wait.max(missing.div_ceil(robots[resource]));
+        }
+        // Building the robot takes an extra minute, and it must have a minute left to collect.
+        if !possible || wait + 1 >= time_left {
+            continue;
+        }
+        let mut next_resources = resources;
+        for resource in [ORE, CLAY, OBSIDIAN, GEODE] {
+            next_resources[resource] += robots[resource] * (wait + 1);
+            next_resources[resource] -= cost[resource];
+        }
+        let mut next_robots = robots;
+        next_robots[robot] += 1;
+        search(bp, max_demand, next_robots, next_resources, time_left - wait - 1, best);
+    }
+}
+
+/// Find the most geodes that can be opened using a blueprint within the time limit, starting
+/// with a single ore robot.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day19::*;
+/// let given_blueprints = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+///     Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+/// Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+///     Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+/// let blueprints = input_generator(given_blueprints).unwrap();
+/// assert_eq!(blueprints.iter().map(|bp| max_geodes(bp, 24)).collect::<Vec<_>>(), vec![9, 12]);
+/// assert_eq!(quality_levels(&blueprints), 33);
+/// assert_eq!(blueprints.iter().map(|bp| max_geodes(bp, 32)).collect::<Vec<_>>(), vec![56, 62]);
+/// ```
+pub fn max_geodes(bp: &Blueprint, minutes: u32) -> u32 {
+    let mut max_demand = [0, 0, 0, u32::MAX];
+    for resource in [ORE, CLAY, OBSIDIAN] {
+        max_demand[resource] = bp.costs.iter().map(|c| c[resource]).max().unwrap_or(0);
+    }
+    let mut best = 0;
+    search(bp, &max_demand, [1, 0, 0, 0], [0; 4], minutes, &mut best);
+    best
+}
+
+/// Sum the quality level of each blueprint, being its ID multiplied by the most geodes it can open
+/// in 24 minutes.
+pub fn quality_levels(blueprints: &[Blueprint]) -> u32 {
+    blueprints.iter().map(|bp| bp.id * max_geodes(bp, 24)).sum()
+}
+
+/// Sum the quality level of each blueprint as in [`quality_levels`], searching the blueprints in
+/// parallel.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day19::*;
+/// let given_blueprints = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+///     Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+/// Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+///     Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
+/// let blueprints = input_generator(given_blueprints).unwrap();
+/// assert_eq!(quality_levels_par(&blueprints), quality_levels(&blueprints));
+/// assert_eq!(quality_levels_par(&blueprints), 33);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn quality_levels_par(blueprints: &[Blueprint]) -> u32 {
+    use rayon::prelude::*;
+
+    blueprints.par_iter().map(|bp| bp.id * max_geodes(bp, 24)).sum()
+}
+
+#[doc(hidden)]
+#[aoc(day19, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 19, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[Blueprint]) -> String {
+    #[cfg(feature = "rayon")]
+    return quality_levels_par(input).to_string();
+    #[cfg(not(feature = "rayon"))]
+    return quality_levels(input).to_string();
+}
+
+#[doc(hidden)]
+#[aoc(day19, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 19, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[Blueprint]) -> String {
+    input.iter().take(3).map(|bp| max_geodes(bp, 32)).product::<u32>().to_string()
+}
+
+// The second part searches 32 minutes of both example blueprints, a few hundred milliseconds in
+// a debug build.
+solution! {
+    day: 19,
+    limit: std::time::Duration::from_secs(2),
+    try_generator: input_generator,
+    example: "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. \
+        Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. \
+        Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.",
+    solve_part1: "33",
+    solve_part2: "3472",
+}
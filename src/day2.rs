@@ -1,75 +1,119 @@
+use alloc::vec::Vec;
+
 use anyhow::{anyhow, Error, Result};
+#[cfg(feature = "std")]
 use aoc_runner_derive::*;
 
+use crate::answer::Answer;
+
 /// Either a Win, Tie, or Loss.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PlayResult {
     Win,
     Tie,
     Loss,
 }
 
-/// A choice between Rock, Paper, and Scissors
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// A choice between Rock, Paper, and Scissors, with Rock being the default.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day2::*;
+/// assert_eq!(Play::default(), Play::Rock);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(C)]
 pub enum Play {
+    #[default]
     Rock = 1,
     Paper,
     Scissors,
 }
 
 impl Play {
+    /// Iterate over every Play.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// let plays = Play::all().collect::<Vec<_>>();
+    /// assert_eq!(plays.len(), 3);
+    /// assert_eq!(plays, vec![Play::Rock, Play::Paper, Play::Scissors]);
+    /// ```
+    pub fn all() -> impl Iterator<Item = Play> {
+        [Play::Rock, Play::Paper, Play::Scissors].into_iter()
+    }
+
+    /// The Play that this Play defeats.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!(Play::Rock.beats(), Play::Scissors);
+    /// assert_eq!(Play::Paper.beats(), Play::Rock);
+    /// assert_eq!(Play::Scissors.beats(), Play::Paper);
+    /// ```
+    pub fn beats(&self) -> Play {
+        match self {
+            Play::Rock => Play::Scissors,
+            Play::Paper => Play::Rock,
+            Play::Scissors => Play::Paper,
+        }
+    }
+
+    /// The Play that defeats this Play.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!(Play::Rock.beaten_by(), Play::Paper);
+    /// assert_eq!(Play::Rock.beaten_by().beats(), Play::Rock);
+    /// ```
+    pub fn beaten_by(&self) -> Play {
+        self.beats().beats()
+    }
+
     /// Given a separate Play, determine whether or not a game between self and the other play
     /// would result in a Tie, Win, or Loss.
     pub fn wins_against(self: &Play, against: &Play) -> PlayResult {
-        match (against, self) {
-            (Play::Rock, Play::Rock) => PlayResult::Tie,
-            (Play::Rock, Play::Paper) => PlayResult::Win,
-            (Play::Rock, Play::Scissors) => PlayResult::Loss,
-            (Play::Paper, Play::Rock) => PlayResult::Loss,
-            (Play::Paper, Play::Paper) => PlayResult::Tie,
-            (Play::Paper, Play::Scissors) => PlayResult::Win,
-            (Play::Scissors, Play::Rock) => PlayResult::Win,
-            (Play::Scissors, Play::Paper) => PlayResult::Loss,
-            (Play::Scissors, Play::Scissors) => PlayResult::Tie,
+        if self == against {
+            PlayResult::Tie
+        } else if self.beats() == *against {
+            PlayResult::Win
+        } else {
+            PlayResult::Loss
         }
     }
 
-    /// Given a strategy of "X", return a losing play; a strategy of "Y", a tying play; a
-    /// strategy of "Z", a winning play.
+    /// Given a Strategy to Lose, Draw, or Win against this Play, return the Play that follows it.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use aoc::day2::*;
     /// let play = Play::Rock;
-    /// let input = ["X", "Y", "Z"];
-    /// let strategies = input.map(|s| Play::from_strategy(&play, s).unwrap());
-    /// assert_eq!(strategies, [Play::Scissors, Play::Rock, Play::Paper]);
+    /// let strategies = [Strategy::Lose, Strategy::Draw, Strategy::Win];
+    /// let plays = strategies.map(|s| Play::from_strategy(&play, s));
+    /// assert_eq!(plays, [Play::Scissors, Play::Rock, Play::Paper]);
     /// ```
-    pub fn from_strategy(from: &Play, strategy: &str) -> Result<Play> {
+    pub fn from_strategy(from: &Play, strategy: Strategy) -> Play {
         match strategy {
-            "X" => match from {
-                Play::Rock => Ok(Play::Scissors),
-                Play::Paper => Ok(Play::Rock),
-                Play::Scissors => Ok(Play::Paper),
-            }
-            "Y" => Ok(*from),
-            "Z" => match from {
-                Play::Rock => Ok(Play::Paper),
-                Play::Paper => Ok(Play::Scissors),
-                Play::Scissors => Ok(Play::Rock),
-            }
-            p => Err(anyhow!("Was given an invalid play: {p}"))
+            Strategy::Lose => from.beats(),
+            Strategy::Draw => *from,
+            Strategy::Win => from.beaten_by(),
         }
     }
 }
 
-impl std::str::FromStr for Play {
+impl core::str::FromStr for Play {
     type Err = Error;
 
-    /// Determine a Play from an input. Note that both Plays and strategies to determine Plays can
-    /// be represented by "X", "Y", and "Z".
+    /// Determine a Play from an input, with "X", "Y", and "Z" being our Plays rather than a
+    /// Strategy.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "A" | "X" => Ok(Play::Rock),
@@ -80,11 +124,111 @@ impl std::str::FromStr for Play {
     }
 }
 
-/// Two Plays pit against each other
-#[derive(Clone, Debug, PartialEq, Eq)]
+impl TryFrom<u8> for Play {
+    type Error = Error;
+
+    /// Determine a Play from its representation, where Rock is 1, Paper is 2, and Scissors is 3.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!(Play::try_from(2u8).unwrap(), Play::Paper);
+    /// assert!(Play::try_from(0u8).is_err());
+    /// for play in Play::all() {
+    ///     assert_eq!(Play::try_from(play as u8).unwrap(), play);
+    /// }
+    /// ```
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Play::Rock),
+            2 => Ok(Play::Paper),
+            3 => Ok(Play::Scissors),
+            p => Err(anyhow!("Was given an invalid play: {p}")),
+        }
+    }
+}
+
+/// Whether our Play should Lose, Draw, or Win against the other Play.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl core::str::FromStr for Strategy {
+    type Err = Error;
+
+    /// Determine a Strategy from an input, where "X" is to Lose, "Y" is to Draw, and "Z" is to
+    /// Win.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!("X".parse::<Strategy>().unwrap(), Strategy::Lose);
+    /// assert_eq!("Y".parse::<Strategy>().unwrap(), Strategy::Draw);
+    /// assert_eq!("Z".parse::<Strategy>().unwrap(), Strategy::Win);
+    /// assert!("A".parse::<Strategy>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Strategy::Lose),
+            "Y" => Ok(Strategy::Draw),
+            "Z" => Ok(Strategy::Win),
+            p => Err(anyhow!("Was given an invalid strategy: {p}")),
+        }
+    }
+}
+
+/// Two Plays pit against each other, with the default Game being Rock against Rock.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day2::*;
+/// assert_eq!(Game::default(), Game(Play::Rock, Play::Rock));
+/// assert_eq!(Game::default().points(), 4);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Game(pub Play, pub Play);
 
 impl Game {
+    /// The result of the round from the right hand perspective of the game.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!(Game(Play::Rock, Play::Paper).outcome(), PlayResult::Win);
+    /// assert_eq!(Game(Play::Paper, Play::Rock).outcome(), PlayResult::Loss);
+    /// assert_eq!(Game(Play::Scissors, Play::Scissors).outcome(), PlayResult::Tie);
+    /// ```
+    pub fn outcome(&self) -> PlayResult {
+        self.1.wins_against(&self.0)
+    }
+
+    /// The index of the player who won the round, with 0 being the left hand player and 1 being
+    /// the right hand player, or None for a tie.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// let given_plays = "A Y\nB X\nC Z";
+    /// let games = input_generator_part1(given_plays);
+    /// let winners = games.iter().map(Game::winner).collect::<Vec<_>>();
+    /// assert_eq!(winners, vec![Some(1), Some(0), None]);
+    /// ```
+    pub fn winner(&self) -> Option<usize> {
+        match self.outcome() {
+            PlayResult::Win => Some(1),
+            PlayResult::Loss => Some(0),
+            PlayResult::Tie => None,
+        }
+    }
+
     /// Points for the shape selected (rock: 1, paper: 2, scissors: 3) and the score for the
     /// outcome of the round, with 0 for a loss, 3 for a tie, and 6 for a win. The points are
     /// calculated from the right hand perspective of the game.
@@ -102,15 +246,176 @@ impl Game {
     /// assert_eq!(points, 15);
     /// ```
     pub fn points(&self) -> u32 {
-        self.1 as u32
-            + match self.1.wins_against(&self.0) {
-                PlayResult::Win => 6,
-                PlayResult::Tie => 3,
-                PlayResult::Loss => 0,
+        self.points_with(&Scoring::default())
+    }
+
+    /// Points for the shape selected and the outcome of the round as in [`Game::points`], but
+    /// using the given Scoring rather than the puzzle's.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// let given_plays = "A Y\nB X\nC Z";
+    /// let games = input_generator_part1(given_plays);
+    /// let scoring = Scoring { win: 10, tie: 5, loss: 0, rock: 3, paper: 2, scissors: 1 };
+    /// let points = games.iter().map(|g| g.points_with(&scoring)).sum::<u32>();
+    /// assert_eq!(points, 21);
+    /// let points = games.iter().map(|g| g.points_with(&Scoring::default())).sum::<u32>();
+    /// assert_eq!(points, 15);
+    /// ```
+    pub fn points_with(&self, scoring: &Scoring) -> u32 {
+        let shape = match self.1 {
+            Play::Rock => scoring.rock,
+            Play::Paper => scoring.paper,
+            Play::Scissors => scoring.scissors,
+        };
+        shape
+            + match self.outcome() {
+                PlayResult::Win => scoring.win,
+                PlayResult::Tie => scoring.tie,
+                PlayResult::Loss => scoring.loss,
             }
     }
 }
 
+impl TryFrom<(char, char)> for Game {
+    type Error = Error;
+
+    /// Determine a Game from a pair of chars, where 'A', 'B', and 'C' are the first player's
+    /// Play, and 'X', 'Y', and 'Z' are our Play.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day2::*;
+    /// assert_eq!(Game::try_from(('A', 'Y')).unwrap(), Game(Play::Rock, Play::Paper));
+    /// assert!(Game::try_from(('X', 'A')).is_err());
+    /// for left in ['A', 'B', 'C'] {
+    ///     for right in ['X', 'Y', 'Z'] {
+    ///         let game = Game::try_from((left, right)).unwrap();
+    ///         assert_eq!(<(char, char)>::from(&game), (left, right));
+    ///     }
+    /// }
+    /// ```
+    fn try_from((left, right): (char, char)) -> Result<Self, Self::Error> {
+        let left = match left {
+            'A' => Play::Rock,
+            'B' => Play::Paper,
+            'C' => Play::Scissors,
+            p => return Err(anyhow!("Was given an invalid play: {p}")),
+        };
+        let right = match right {
+            'X' => Play::Rock,
+            'Y' => Play::Paper,
+            'Z' => Play::Scissors,
+            p => return Err(anyhow!("Was given an invalid play: {p}")),
+        };
+        Ok(Game(left, right))
+    }
+}
+
+impl From<&Game> for (char, char) {
+    /// Represent a Game as the pair of chars it would be parsed from.
+    fn from(game: &Game) -> (char, char) {
+        let left = match game.0 {
+            Play::Rock => 'A',
+            Play::Paper => 'B',
+            Play::Scissors => 'C',
+        };
+        let right = match game.1 {
+            Play::Rock => 'X',
+            Play::Paper => 'Y',
+            Play::Scissors => 'Z',
+        };
+        (left, right)
+    }
+}
+
+/// The points given for each outcome of a Game, and for each shape selected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scoring {
+    pub win: u32,
+    pub tie: u32,
+    pub loss: u32,
+    pub rock: u32,
+    pub paper: u32,
+    pub scissors: u32,
+}
+
+impl Default for Scoring {
+    /// The puzzle's scoring, where shapes are worth their Play representation.
+    fn default() -> Scoring {
+        Scoring {
+            win: 6,
+            tie: 3,
+            loss: 0,
+            rock: Play::Rock as u32,
+            paper: Play::Paper as u32,
+            scissors: Play::Scissors as u32,
+        }
+    }
+}
+
+/// Find every Game whose points, from the right hand perspective, equal the given points.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day2::*;
+/// assert_eq!(games_with_points(8), vec![Game(Play::Rock, Play::Paper)]);
+/// assert!(games_with_points(10).is_empty());
+///
+/// for left in Play::all() {
+///     for right in Play::all() {
+///         let game = Game(left, right);
+///         assert!(games_with_points(game.points()).contains(&game));
+///     }
+/// }
+/// ```
+pub fn games_with_points(points: u32) -> Vec<Game> {
+    Play::all()
+        .flat_map(|left| Play::all().map(move |right| Game(left, right)))
+        .filter(|game| game.points() == points)
+        .collect()
+}
+
+/// Play every Play against every other Play once, returning each Play's index along with its
+/// total points from those games, from the highest total to the lowest. Plays with equal totals
+/// keep their original order.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day2::*;
+/// let plays = [Play::Rock, Play::Paper, Play::Scissors];
+/// assert_eq!(tournament(&plays), vec![(2, 12), (1, 10), (0, 8)]);
+///
+/// // Each Play wins once and loses once, so only the points for each shape differ.
+/// for (i, points) in tournament(&plays) {
+///     assert_eq!(points - 2 * plays[i] as u32, 6);
+/// }
+///
+/// assert_eq!(tournament(&[Play::Rock, Play::Rock]), vec![(0, 4), (1, 4)]);
+/// ```
+pub fn tournament(plays: &[Play]) -> Vec<(usize, u32)> {
+    let mut scores = plays
+        .iter()
+        .enumerate()
+        .map(|(i, play)| {
+            let points = plays
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, other)| Game(*other, *play).points())
+                .sum();
+            (i, points)
+        })
+        .collect::<Vec<_>>();
+    scores.sort_by_key(|(_, points)| core::cmp::Reverse(*points));
+    scores
+}
+
 /// Given an input in the form of Plays, where A, B, and C are the first player's Rock, Paper, or
 /// Scissor, and X, Y, and Z are our Rock, Paper, or Scissor, generate a Vec of Games.
 ///
@@ -126,7 +431,11 @@ impl Game {
 ///     Game(Play::Scissors, Play::Scissors)
 /// ]);
 /// ```
-#[aoc_generator(day2, part1)]
+#[cfg_attr(feature = "std", aoc_generator(day2, part1))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 2, part = 1, input_len = input.len()))
+)]
 pub fn input_generator_part1(input: &str) -> Vec<Game> {
     input
         .lines()
@@ -161,7 +470,11 @@ pub fn input_generator_part1(input: &str) -> Vec<Game> {
 ///     Game(Play::Scissors, Play::Rock)
 /// ]);
 /// ```
-#[aoc_generator(day2, part2)]
+#[cfg_attr(feature = "std", aoc_generator(day2, part2))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 2, part = 2, input_len = input.len()))
+)]
 pub fn input_generator_part2(input: &str) -> Vec<Game> {
     input
         .lines()
@@ -171,30 +484,47 @@ pub fn input_generator_part2(input: &str) -> Vec<Game> {
                 .next()?
                 .parse()
                 .ok()?;
-            let play_right = split
-                .next()
-                .and_then(|s| Play::from_strategy(&play_left, s).ok())?;
+            let strategy = split
+                .next()?
+                .parse()
+                .ok()?;
+            let play_right = Play::from_strategy(&play_left, strategy);
             Some(Game(play_left, play_right))
         })
         .collect()
 }
 
 #[doc(hidden)]
-#[aoc(day2, part1)]
-pub fn solve_part1(input: &[Game]) -> String {
+#[cfg_attr(feature = "std", aoc(day2, part1))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 2, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[Game]) -> Answer {
     let total: u32 = input
         .iter()
         .map(|g| g.points())
         .sum();
-    total.to_string()
+    total.into()
 }
 
 #[doc(hidden)]
-#[aoc(day2, part2)]
-pub fn solve_part2(input: &[Game]) -> String {
+#[cfg_attr(feature = "std", aoc(day2, part2))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 2, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[Game]) -> Answer {
     let points: u32 = input
         .iter()
         .map(|g| g.points())
         .sum();
-    points.to_string()
+    points.into()
+}
+
+solution! {
+    day: 2,
+    example: "A Y\nB X\nC Z",
+    solve_part1(input_generator_part1): Answer::Int(15),
+    solve_part2(input_generator_part2): Answer::Int(12),
 }
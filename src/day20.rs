@@ -0,0 +1,184 @@
+use anyhow::Result;
+use aoc_runner_derive::*;
+
+use crate::fenwick::Fenwick;
+
+/// The key each number is multiplied by before mixing in part 2.
+pub const DECRYPTION_KEY: i64 = 811589153;
+
+/// Given an input in the form of one number per line, generate a Vec of numbers.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day20::*;
+/// assert_eq!(input_generator("1\n2\n-3").unwrap(), vec![1, 2, -3]);
+/// ```
+#[aoc_generator(day20)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 20, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Vec<i64>> {
+    Ok(input.lines().map(str::parse).collect::<Result<_, _>>()?)
+}
+
+/// A list of original indices split into blocks of roughly `sqrt(n)` items each, so that finding,
+/// removing, and inserting an item only has to scan a single block. The size of each block is
+/// kept in a Fenwick tree, to find where a block starts and which block holds a position.
+struct Blocks {
+    blocks: Vec<Vec<usize>>,
+    block_of: Vec<usize>,
+    block_size: usize,
+    sizes: Fenwick,
+}
+
+impl Blocks {
+    fn new(items: impl Iterator<Item = usize>, len: usize) -> Blocks {
+        let mut blocks = Blocks {
+            blocks: vec![],
+            block_of: vec![0; len],
+            block_size: len.isqrt() + 1,
+            sizes: Fenwick::new(0),
+        };
+        blocks.rebuild(items.collect());
+        blocks
+    }
+
+    /// Split the items back into evenly sized blocks, once inserts have made a block too large.
+    fn rebuild(&mut self, items: Vec<usize>) {
+        self.blocks = items.chunks(self.block_size).map(Vec::from).collect();
+        for (b, block) in self.blocks.iter().enumerate() {
+            for item in block {
+                self.block_of[*item] = b;
+            }
+        }
+        let sizes = self.blocks.iter().map(|block| block.len() as i64).collect::<Vec<_>>();
+        self.sizes = Fenwick::from(&sizes[..]);
+    }
+
+    /// Remove an item, returning the position it was at.
+    fn remove(&mut self, item: usize) -> usize {
+        let b = self.block_of[item];
+        let offset = self.blocks[b].iter().position(|i| *i == item).expect("lost a number");
+        self.blocks[b].remove(offset);
+        self.sizes.add(b, -1);
+        self.sizes.prefix_sum(b) as usize + offset
+    }
+
+    /// Insert an item so that it ends up at the given position.
+    fn insert(&mut self, position: usize, item: usize) {
+        // Inserting after every item appends to the last block.
+        let b = self.sizes.find_kth(position as i64).unwrap_or(self.blocks.len() - 1);
+        let offset = position - self.sizes.prefix_sum(b) as usize;
+        self.blocks[b].insert(offset, item);
+        self.sizes.add(b, 1);
+        self.block_of[item] = b;
+        if self.blocks[b].len() > 2 * self.block_size {
+            let items = self.blocks.concat();
+            self.rebuild(items);
+        }
+    }
+}
+
+/// Mix a circular list of numbers by moving each number, in the order they originally appeared,
+/// forwards or backwards by its own value, repeating for the given number of rounds. Since numbers
+/// may repeat, each number is tracked by its original index rather than by its value. As the list
+/// is circular, the mixed list may start at any point in the cycle.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day20::*;
+/// let numbers = input_generator("1\n2\n-3\n3\n-2\n0\n4").unwrap();
+/// let mut mixed = mix(&numbers, 1);
+/// let zero = mixed.iter().position(|n| *n == 0).unwrap();
+/// mixed.rotate_left(zero);
+/// assert_eq!(mixed, vec![0, 3, -2, 1, 2, -3, 4]);
+///
+/// let numbers = (0..500).map(|n| (n * 7919) % 1013 - 500).collect::<Vec<i64>>();
+/// assert_eq!(mix(&numbers, 3), mix_naive(&numbers, 3));
+/// ```
+pub fn mix(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    if numbers.len() < 2 {
+        return Vec::from(numbers);
+    }
+    // Moving an item all the way around the list, minus itself, lands it back where it started.
+    let modulus = numbers.len() as i64 - 1;
+    let mut blocks = Blocks::new(0..numbers.len(), numbers.len());
+    for _ in 0..rounds {
+        for (original, value) in numbers.iter().enumerate() {
+            let position = blocks.remove(original);
+            // Reduce the value first, so large values can't overflow when offset.
+            let destination = (position as i64 + value.rem_euclid(modulus)) % modulus;
+            blocks.insert(destination as usize, original);
+        }
+    }
+    blocks.blocks.concat().into_iter().map(|i| numbers[i]).collect()
+}
+
+/// Mix a circular list of numbers as in [`mix`], by removing and inserting into a single Vec. Each
+/// move takes linear time, so this is kept only as a reference to check and benchmark against.
+pub fn mix_naive(numbers: &[i64], rounds: usize) -> Vec<i64> {
+    let mut list = numbers.iter().copied().enumerate().collect::<Vec<_>>();
+    let modulus = numbers.len() as i64 - 1;
+    for _ in 0..rounds {
+        for original in 0..numbers.len() {
+            let position = list.iter().position(|(i, _)| *i == original).expect("lost a number");
+            let item = list.remove(position);
+            if modulus > 0 {
+                let destination = (position as i64 + item.1.rem_euclid(modulus)) % modulus;
+                list.insert(destination as usize, item);
+            } else {
+                list.push(item);
+            }
+        }
+    }
+    list.into_iter().map(|(_, value)| value).collect()
+}
+
+/// Sum the numbers 1000, 2000, and 3000 positions after the `0` in a mixed list.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day20::*;
+/// let numbers = input_generator("1\n2\n-3\n3\n-2\n0\n4").unwrap();
+/// assert_eq!(grove_coordinates(&mix(&numbers, 1)), Some(3));
+///
+/// let decrypted = numbers.iter().map(|n| n * DECRYPTION_KEY).collect::<Vec<_>>();
+/// assert_eq!(grove_coordinates(&mix(&decrypted, 10)), Some(1623178306));
+/// ```
+pub fn grove_coordinates(mixed: &[i64]) -> Option<i64> {
+    let zero = mixed.iter().position(|n| *n == 0)?;
+    Some([1000, 2000, 3000].iter().map(|offset| mixed[(zero + offset) % mixed.len()]).sum())
+}
+
+#[doc(hidden)]
+#[aoc(day20, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 20, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &[i64]) -> String {
+    grove_coordinates(&mix(input, 1)).expect("no zero in input").to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day20, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 20, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &[i64]) -> String {
+    let decrypted = input.iter().map(|n| n * DECRYPTION_KEY).collect::<Vec<_>>();
+    grove_coordinates(&mix(&decrypted, 10)).expect("no zero in input").to_string()
+}
+
+solution! {
+    day: 20,
+    try_generator: input_generator,
+    example: "1\n2\n-3\n3\n-2\n0\n4",
+    solve_part1: "3",
+    solve_part2: "1623178306",
+}
@@ -0,0 +1,291 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, Error, Result};
+use aoc_runner_derive::*;
+use num_rational::Rational64;
+
+/// An arithmetic operation performed by a monkey on the numbers yelled by two other monkeys.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    /// Apply the operation to two numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day21::*;
+    /// assert_eq!(Op::Sub.apply(7, 2).unwrap(), 5);
+    /// assert_eq!(Op::Div.apply(7, 2).unwrap(), 3);
+    /// assert!(Op::Div.apply(7, 0).is_err());
+    /// ```
+    pub fn apply(&self, left: i64, right: i64) -> Result<i64> {
+        match self {
+            Op::Add => Ok(left + right),
+            Op::Sub => Ok(left - right),
+            Op::Mul => Ok(left * right),
+            Op::Div if right == 0 => Err(anyhow!("Attempted to divide {left} by zero")),
+            Op::Div => Ok(left / right),
+        }
+    }
+}
+
+impl std::str::FromStr for Op {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Op::Add),
+            "-" => Ok(Op::Sub),
+            "*" => Ok(Op::Mul),
+            "/" => Ok(Op::Div),
+            op => Err(anyhow!("Was given an invalid operation: {op}")),
+        }
+    }
+}
+
+/// What a monkey yells: either a number, or the result of an operation on the numbers yelled by
+/// two other monkeys.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Literal(i64),
+    Op(String, Op, String),
+}
+
+impl std::str::FromStr for Expr {
+    type Err = Error;
+
+    /// Parse an expression such as `5` or `pppw + sjmn`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            [n] => Ok(Expr::Literal(n.parse()?)),
+            [left, op, right] => Ok(Expr::Op(left.to_string(), op.parse()?, right.to_string())),
+            _ => Err(anyhow!("Was given an invalid expression: {s}")),
+        }
+    }
+}
+
+/// Given an input in the form of `name: expression` lines, generate a map of monkey names to the
+/// expressions they yell.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day21::*;
+/// let monkeys = input_generator("root: pppw + sjmn\ndbpl: 5").unwrap();
+/// assert_eq!(monkeys["root"], Expr::Op("pppw".to_string(), Op::Add, "sjmn".to_string()));
+/// assert_eq!(monkeys["dbpl"], Expr::Literal(5));
+/// ```
+#[aoc_generator(day21)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 21, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<HashMap<String, Expr>> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, expr) = line
+                .split_once(": ")
+                .ok_or_else(|| anyhow!("Was given an invalid monkey: {line}"))?;
+            Ok((name.to_string(), expr.parse()?))
+        })
+        .collect()
+}
+
+/// Evaluate the number yelled by the named monkey. Monkeys are evaluated with an explicit stack,
+/// remembering each result, so deep chains of monkeys can't overflow the call stack. Monkeys
+/// waiting on others are tracked, so a monkey that ends up waiting on itself is reported as a
+/// cycle rather than looping forever.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day21::*;
+/// let given_monkeys = "root: pppw + sjmn\ndbpl: 5\ncczh: sllz + lgvd\nzczc: 2\nptdq: humn - dvpt
+/// dvpt: 3\nlfqf: 4\nhumn: 5\nljgn: 2\nsjmn: drzm * dbpl\nsllz: 4\npppw: cczh / lfqf
+/// lgvd: ljgn * ptdq\ndrzm: hmdt - zczc\nhmdt: 32";
+/// let monkeys = input_generator(given_monkeys).unwrap();
+/// assert_eq!(evaluate(&monkeys, "root").unwrap(), 152);
+/// assert_eq!(evaluate(&monkeys, "sjmn").unwrap(), 150);
+/// assert!(evaluate(&monkeys, "nope").is_err());
+///
+/// let monkeys = input_generator("root: aaaa + bbbb\naaaa: 3").unwrap();
+/// assert_eq!(evaluate(&monkeys, "root").unwrap_err().to_string(), "Undefined monkey: bbbb");
+///
+/// let given_monkeys = "root: aaaa + bbbb\naaaa: bbbb * cccc\nbbbb: aaaa - cccc\ncccc: 1";
+/// let monkeys = input_generator(given_monkeys).unwrap();
+/// let error = evaluate(&monkeys, "root").unwrap_err();
+/// assert_eq!(error.to_string(), "cycle detected at monkey bbbb");
+/// ```
+pub fn evaluate(monkeys: &HashMap<String, Expr>, name: &str) -> Result<i64> {
+    let mut values: HashMap<&str, i64> = HashMap::new();
+    let mut waiting: HashSet<&str> = HashSet::new();
+    let mut stack = vec![name];
+    while let Some(&current) = stack.last() {
+        if values.contains_key(current) {
+            stack.pop();
+            continue;
+        }
+        match monkeys.get(current).ok_or_else(|| anyhow!("Undefined monkey: {current}"))? {
+            Expr::Literal(n) => {
+                values.insert(current, *n);
+                stack.pop();
+            }
+            Expr::Op(left, op, right) => {
+                match (values.get(left.as_str()), values.get(right.as_str())) {
+                    (Some(l), Some(r)) => {
+                        let value = op.apply(*l, *r)?;
+                        values.insert(current, value);
+                        stack.pop();
+                    }
+                    (l, r) => {
+                        waiting.insert(current);
+                        for (value, name) in [(l, left), (r, right)] {
+                            if value.is_some() {
+                                continue;
+                            }
+                            if waiting.contains(name.as_str()) {
+                                return Err(anyhow!("cycle detected at monkey {name}"));
+                            }
+                            stack.push(name);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(values[name])
+}
+
+/// Find the number `humn` must yell for both sides of `root` to be equal. The path of monkeys
+/// from `root` down to `humn` is found first, then each monkey along it is inverted: the side
+/// without `humn` is evaluated as a constant, and the operation is undone to find what the side
+/// with `humn` must be. Inverting is done with exact fractions, so undoing an operation may give
+/// a fraction as long as `humn` itself ends up a whole number.
+///
+/// # Panics
+///
+/// If `humn` is not reachable from `root`, the monkeys above `humn` form a cycle, any monkey can't
+/// be evaluated, inverting a monkey would divide by zero, or `humn` would have to yell a fraction,
+/// the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day21::*;
+/// let given_monkeys = "root: pppw + sjmn\ndbpl: 5\ncczh: sllz + lgvd\nzczc: 2\nptdq: humn - dvpt
+/// dvpt: 3\nlfqf: 4\nhumn: 5\nljgn: 2\nsjmn: drzm * dbpl\nsllz: 4\npppw: cczh / lfqf
+/// lgvd: ljgn * ptdq\ndrzm: hmdt - zczc\nhmdt: 32";
+/// let monkeys = input_generator(given_monkeys).unwrap();
+/// assert_eq!(solve_for_humn(&monkeys), 301);
+///
+/// // The unknown on the right hand side of subtraction and division is inverted differently.
+/// let monkeys = input_generator("root: a + b\nb: 8\na: c - e\nc: 100\ne: f / humn\nf: 184\n\
+///     humn: 0").unwrap();
+/// assert_eq!(solve_for_humn(&monkeys), 2);
+///
+/// // `c` must be 3/2, which rounding down to 1 would turn into a `humn` of 4.
+/// let monkeys = input_generator("root: a + b\nb: 3\na: c * d\nd: 2\nc: humn / e\ne: 4\n\
+///     humn: 0").unwrap();
+/// assert_eq!(solve_for_humn(&monkeys), 6);
+/// ```
+///
+/// ```rust,should_panic
+/// # use aoc::day21::*;
+/// // `humn` waits on `aaaa`, which waits on `humn`, without `root` ever reaching either.
+/// let given_monkeys = "root: bbbb + cccc\nhumn: aaaa * bbbb\naaaa: humn + cccc\nbbbb: 1\ncccc: 2";
+/// solve_for_humn(&input_generator(given_monkeys).unwrap());
+/// ```
+///
+/// ```rust,should_panic
+/// # use aoc::day21::*;
+/// // Nothing times zero can make 5.
+/// let monkeys = input_generator("root: a + b\nb: 5\na: humn * c\nc: 0\nhumn: 1").unwrap();
+/// solve_for_humn(&monkeys);
+/// ```
+pub fn solve_for_humn(monkeys: &HashMap<String, Expr>) -> i64 {
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    for (name, expr) in monkeys {
+        if let Expr::Op(left, _, right) = expr {
+            parents.insert(left.as_str(), name.as_str());
+            parents.insert(right.as_str(), name.as_str());
+        }
+    }
+    let mut path = vec!["humn"];
+    let mut visited = HashSet::from(["humn"]);
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        assert!(visited.insert(parent), "cycle detected at monkey {parent}");
+        path.push(parent);
+    }
+    assert_eq!(path.last(), Some(&"root"), "humn is not reachable from root");
+    path.reverse();
+
+    let mut target = None;
+    for window in path.windows(2) {
+        let (current, unknown) = (window[0], window[1]);
+        let Some(Expr::Op(left, op, right)) = monkeys.get(current) else {
+            panic!("monkey {current} has no operation");
+        };
+        let humn_on_left = left == unknown;
+        let known = evaluate(monkeys, if humn_on_left { right } else { left })
+            .expect("couldn't evaluate constant side");
+        let known = Rational64::from_integer(known);
+        if let Some(t) = target {
+            let zero = match (op, humn_on_left) {
+                (Op::Mul, _) | (Op::Div, true) => known == 0.into(),
+                (Op::Div, false) => t == 0.into(),
+                _ => false,
+            };
+            assert!(!zero, "monkey {current} can't be inverted, as it would divide by zero");
+        }
+        target = Some(match (target, op, humn_on_left) {
+            // Both sides of root must be equal.
+            (None, _, _) => known,
+            (Some(t), Op::Add, _) => t - known,
+            (Some(t), Op::Mul, _) => t / known,
+            (Some(t), Op::Sub, true) => t + known,
+            (Some(t), Op::Sub, false) => known - t,
+            (Some(t), Op::Div, true) => t * known,
+            (Some(t), Op::Div, false) => known / t,
+        });
+    }
+    let humn = target.expect("humn can't be root");
+    assert!(humn.is_integer(), "humn would have to yell {humn}");
+    humn.to_integer()
+}
+
+#[doc(hidden)]
+#[aoc(day21, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 21, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &HashMap<String, Expr>) -> String {
+    evaluate(input, "root").expect("couldn't evaluate root").to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day21, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 21, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &HashMap<String, Expr>) -> String {
+    solve_for_humn(input).to_string()
+}
+
+solution! {
+    day: 21,
+    try_generator: input_generator,
+    example: "root: pppw + sjmn\ndbpl: 5\ncczh: sllz + lgvd\nzczc: 2\nptdq: humn - dvpt
+dvpt: 3\nlfqf: 4\nhumn: 5\nljgn: 2\nsjmn: drzm * dbpl\nsllz: 4\npppw: cczh / lfqf
+lgvd: ljgn * ptdq\ndrzm: hmdt - zczc\nhmdt: 32",
+    solve_part1: "152",
+    solve_part2: "301",
+}
@@ -0,0 +1,332 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::cube::{detect_faces, neg, CubeLayout};
+use crate::grid::Grid;
+use crate::point::{Direction, Point};
+
+/// A single instruction along the path: walk forward some number of tiles, or turn in place.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+/// A map of open tiles (`.`) and walls (`#`), with rows of differing widths. Spaces, and anything
+/// past the end of a row, are not part of the map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MonkeyMap {
+    pub rows: Vec<Vec<u8>>,
+    pub path: Vec<Step>,
+}
+
+impl MonkeyMap {
+    /// Get the tile at a point, or None if the point is not part of the map.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day22::*;
+    /// # use aoc::point::Point;
+    /// let map = input_generator("  .#\n...\n\n1").unwrap();
+    /// assert_eq!(map.tile(Point::new(0, 0)), None);
+    /// assert_eq!(map.tile(Point::new(3, 0)), Some(b'#'));
+    /// assert_eq!(map.tile(Point::new(3, 1)), None);
+    /// assert_eq!(map.tile(Point::new(-1, 1)), None);
+    /// ```
+    pub fn tile(&self, point: Point) -> Option<u8> {
+        let row = self.rows.get(usize::try_from(point.y).ok()?)?;
+        row.get(usize::try_from(point.x).ok()?).copied().filter(|t| *t != b' ')
+    }
+
+    /// The leftmost open tile of the top row, where the path starts.
+    pub fn start(&self) -> Option<Point> {
+        let x = self.rows.first()?.iter().position(|t| *t == b'.')?;
+        Some(Point::new(x as i64, 0))
+    }
+
+    /// Wrap around to the opposite side of the map when stepping off an edge, by walking back in
+    /// the opposite direction to the last tile in the same row or column. Spaces are not tiles,
+    /// so a row with leading spaces wraps to its first non-space tile rather than to column 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day22::*;
+    /// # use aoc::point::Point;
+    /// let map = input_generator("  ....\n......\n\nRR1").unwrap();
+    /// assert_eq!(map.wrap_flat(Point::new(2, 0), 2), (Point::new(5, 0), 2));
+    /// assert_eq!(map.wrap_flat(Point::new(5, 0), 0), (Point::new(2, 0), 0));
+    /// assert_eq!(map.wrap_flat(Point::new(1, 1), 3), (Point::new(1, 1), 3));
+    /// let (position, facing) = map.walk(|p, f| map.wrap_flat(p, f)).unwrap();
+    /// assert_eq!(password(position, facing), 1000 + 4 * 6 + 2);
+    ///
+    /// // Only a wall blocks wrapping, leaving the position where it was.
+    /// let map = input_generator("  ...#\n\nRR1").unwrap();
+    /// let (position, facing) = map.walk(|p, f| map.wrap_flat(p, f)).unwrap();
+    /// assert_eq!((position, facing), (Point::new(2, 0), 2));
+    /// assert_eq!(password(position, facing), 1000 + 4 * 3 + 2);
+    /// ```
+    pub fn wrap_flat(&self, point: Point, facing: u8) -> (Point, u8) {
+        let back = Direction::from_facing(facing).opposite().delta();
+        let mut current = point;
+        while self.tile(current + back).is_some() {
+            current = current + back;
+        }
+        (current, facing)
+    }
+
+    /// Follow the path from the start, using the given function to find where to go when
+    /// stepping off the edge of the map, and return the final position and facing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day22::*;
+    /// # use aoc::point::Point;
+    /// let given_map = concat!(
+    ///     "        ...#\n",
+    ///     "        .#..\n",
+    ///     "        #...\n",
+    ///     "        ....\n",
+    ///     "...#.......#\n",
+    ///     "........#...\n",
+    ///     "..#....#....\n",
+    ///     "..........#.\n",
+    ///     "        ...#....\n",
+    ///     "        .....#..\n",
+    ///     "        .#......\n",
+    ///     "        ......#.\n",
+    ///     "\n",
+    ///     "10R5L5R10L4R5L5",
+    /// );
+    /// let map = input_generator(given_map).unwrap();
+    /// let (position, facing) = map.walk(|p, f| map.wrap_flat(p, f)).unwrap();
+    /// assert_eq!((position, facing), (Point::new(7, 5), 0));
+    /// assert_eq!(password(position, facing), 6032);
+    /// ```
+    pub fn walk(&self, wrap: impl Fn(Point, u8) -> (Point, u8)) -> Option<(Point, u8)> {
+        let mut position = self.start()?;
+        let mut facing = 0;
+        for step in &self.path {
+            match step {
+                Step::Left => facing = Direction::from_facing(facing).turn_left().facing(),
+                Step::Right => facing = Direction::from_facing(facing).turn_right().facing(),
+                Step::Forward(n) => {
+                    for _ in 0..*n {
+                        let mut next = (position + Direction::from_facing(facing).delta(), facing);
+                        if self.tile(next.0).is_none() {
+                            next = wrap(position, facing);
+                        }
+                        if self.tile(next.0) == Some(b'#') {
+                            break;
+                        }
+                        (position, facing) = next;
+                    }
+                }
+            }
+        }
+        Some((position, facing))
+    }
+
+    /// Detect the faces of the cube in the map with [`detect_faces`], finding the size of each
+    /// face from the number of tiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::day22::*;
+    /// # use aoc::point::Point;
+    /// // A cross-shaped net; each face is opposite of the face with the negated normal.
+    /// let map = input_generator(" .\n...\n .\n .\n\n1").unwrap();
+    /// let layout = map.cube_layout().unwrap();
+    /// assert_eq!(layout.size, 1);
+    /// assert_eq!(layout.faces.len(), 6);
+    /// let top = layout.face_at(Point::new(1, 1)).unwrap();
+    /// let bottom = layout.face_at(Point::new(1, 3)).unwrap();
+    /// assert_eq!(top.normal.map(|n| -n), bottom.normal);
+    ///
+    /// assert!(input_generator("..\n\n1").unwrap().cube_layout().is_err());
+    /// // A strip of six faces wraps around onto itself rather than folding into a cube.
+    /// let strip = input_generator("......\n\n1").unwrap();
+    /// let error = strip.cube_layout().unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Map net folds more than one face onto the same side of the cube"
+    /// );
+    /// ```
+    pub fn cube_layout(&self) -> Result<CubeLayout> {
+        let tiles = self.rows.iter().flatten().filter(|t| **t != b' ').count();
+        let size = ((tiles / 6) as f64).sqrt() as usize;
+        if size == 0 || size * size * 6 != tiles {
+            return Err(anyhow!("Map with {tiles} tiles can't be folded into a cube"));
+        }
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|t| *t as char).collect())
+            .collect::<Vec<_>>();
+        let layout = detect_faces(&Grid::from_rows(&rows, ' '), size);
+        if layout.faces.len() != 6 {
+            return Err(anyhow!("Map net has {} faces, not 6", layout.faces.len()));
+        }
+        let normals = layout.faces.iter().map(|f| f.normal).collect::<HashSet<_>>();
+        if normals.len() != 6 {
+            return Err(anyhow!("Map net folds more than one face onto the same side of the cube"));
+        }
+        Ok(layout)
+    }
+}
+
+/// Wrap around the edge of a cube when stepping off an edge of the map, returning the position
+/// and facing on the face that shares that edge once the net is folded.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day22::*;
+/// # use aoc::point::Point;
+/// let given_map = concat!(
+///     "        ...#\n",
+///     "        .#..\n",
+///     "        #...\n",
+///     "        ....\n",
+///     "...#.......#\n",
+///     "........#...\n",
+///     "..#....#....\n",
+///     "..........#.\n",
+///     "        ...#....\n",
+///     "        .....#..\n",
+///     "        .#......\n",
+///     "        ......#.\n",
+///     "\n",
+///     "10R5L5R10L4R5L5",
+/// );
+/// let map = input_generator(given_map).unwrap();
+/// let layout = map.cube_layout().unwrap();
+/// // Walking right off of A, from the puzzle's example, lands on B facing down.
+/// assert_eq!(wrap_cube(Point::new(11, 5), 0, &layout), (Point::new(14, 8), 1));
+/// // Walking down off of C lands on D facing up.
+/// assert_eq!(wrap_cube(Point::new(10, 11), 1, &layout), (Point::new(1, 7), 3));
+///
+/// let (position, facing) = map.walk(|p, f| wrap_cube(p, f, &layout)).unwrap();
+/// assert_eq!(password(position, facing), 5031);
+/// ```
+pub fn wrap_cube(pos: Point, facing: u8, faces: &CubeLayout) -> (Point, u8) {
+    let from = faces.face_at(pos).expect("position is not on a face");
+    let to = faces.adjacent(from, facing).expect("cube is missing a face");
+    // Having walked over the edge, we're now heading back down the side of the cube.
+    let heading = neg(from.normal);
+    let new_facing = (0..4).find(|f| to.direction(*f) == heading).expect("face is not square");
+
+    let local = Point::new(pos.x - from.origin.x, pos.y - from.origin.y);
+    let offset = if facing.is_multiple_of(2) { local.y } else { local.x };
+    let last = faces.size - 1;
+    let offset = if from.along(facing) == to.along(new_facing) { offset } else { last - offset };
+    let local = match new_facing {
+        0 => Point::new(0, offset),
+        1 => Point::new(offset, 0),
+        2 => Point::new(last, offset),
+        _ => Point::new(offset, last),
+    };
+    (to.origin + local, new_facing)
+}
+
+/// The password for a final position and facing, using 1-based rows and columns.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day22::*;
+/// # use aoc::point::Point;
+/// assert_eq!(password(Point::new(7, 5), 0), 6032);
+/// ```
+pub fn password(position: Point, facing: u8) -> i64 {
+    1000 * (position.y + 1) + 4 * (position.x + 1) + facing as i64
+}
+
+/// Given an input in the form of a map followed by a blank line and a path, such as `10R5L5`,
+/// generate a MonkeyMap.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day22::*;
+/// let map = input_generator("  ..\n.#.\n\n10R5L").unwrap();
+/// assert_eq!(map.rows, vec![b"  ..".to_vec(), b".#.".to_vec()]);
+/// assert_eq!(map.path, vec![Step::Forward(10), Step::Right, Step::Forward(5), Step::Left]);
+/// ```
+#[aoc_generator(day22)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 22, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<MonkeyMap> {
+    let (map, path) =
+        input.split_once("\n\n").ok_or_else(|| anyhow!("Was given a map with no path"))?;
+    let rows = map.lines().map(|line| line.as_bytes().to_vec()).collect();
+    let mut steps = vec![];
+    let mut number = String::new();
+    for ch in path.trim().chars() {
+        if ch.is_ascii_digit() {
+            number.push(ch);
+            continue;
+        }
+        if !number.is_empty() {
+            steps.push(Step::Forward(number.parse()?));
+            number.clear();
+        }
+        match ch {
+            'L' => steps.push(Step::Left),
+            'R' => steps.push(Step::Right),
+            c => return Err(anyhow!("Was given an invalid step: {c}")),
+        }
+    }
+    if !number.is_empty() {
+        steps.push(Step::Forward(number.parse()?));
+    }
+    Ok(MonkeyMap { rows, path: steps })
+}
+
+#[doc(hidden)]
+#[aoc(day22, part1)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 22, part = 1)))]
+pub fn solve_part1(input: &MonkeyMap) -> String {
+    let (position, facing) = input.walk(|p, f| input.wrap_flat(p, f)).expect("map has no start");
+    password(position, facing).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day22, part2)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 22, part = 2)))]
+pub fn solve_part2(input: &MonkeyMap) -> String {
+    let layout = input.cube_layout().expect("map is not a cube net");
+    let (position, facing) = input.walk(|p, f| wrap_cube(p, f, &layout)).expect("map has no start");
+    password(position, facing).to_string()
+}
+
+solution! {
+    day: 22,
+    try_generator: input_generator,
+    example: concat!(
+        "        ...#\n",
+        "        .#..\n",
+        "        #...\n",
+        "        ....\n",
+        "...#.......#\n",
+        "........#...\n",
+        "..#....#....\n",
+        "..........#.\n",
+        "        ...#....\n",
+        "        .....#..\n",
+        "        .#......\n",
+        "        ......#.\n",
+        "\n",
+        "10R5L5R10L4R5L5",
+    ),
+    solve_part1: "6032",
+    solve_part2: "5031",
+}
@@ -0,0 +1,312 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc_runner_derive::*;
+
+use crate::point::Point;
+use crate::simulate::iterate_until_stable;
+
+/// The directions elves consider moving in, in the order they're considered on the first round,
+/// along with the three cells that must be empty to move in that direction.
+const DIRECTIONS: [[Point; 3]; 4] = [
+    [Point { x: 0, y: -1 }, Point { x: -1, y: -1 }, Point { x: 1, y: -1 }],
+    [Point { x: 0, y: 1 }, Point { x: -1, y: 1 }, Point { x: 1, y: 1 }],
+    [Point { x: -1, y: 0 }, Point { x: -1, y: -1 }, Point { x: -1, y: 1 }],
+    [Point { x: 1, y: 0 }, Point { x: 1, y: -1 }, Point { x: 1, y: 1 }],
+];
+
+/// Given an input in the form of a grid where `#` is an elf, generate the set of elf positions.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day23::*;
+/// # use aoc::point::Point;
+/// let elves = input_generator(".#\n#.");
+/// assert_eq!(elves, [Point::new(1, 0), Point::new(0, 1)].into());
+/// ```
+#[aoc_generator(day23)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 23, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> HashSet<Point> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| Point::new(x as i64, y as i64))
+        })
+        .collect()
+}
+
+/// Run a single round, with `round` counting from 0 to decide which direction is considered
+/// first. Each elf with any neighbor proposes moving in the first direction with no elves, and
+/// elves only move if no other elf proposed moving to the same spot. Returns whether any elf
+/// moved.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day23::*;
+/// # use aoc::point::Point;
+/// let mut elves = input_generator(".....\n..##.\n..#..\n.....\n..##.\n.....");
+/// assert!(run_round(&mut elves, 0));
+/// assert_eq!(elves, input_generator("..##.\n.....\n..#..\n...#.\n..#..\n....."));
+/// ```
+pub fn run_round(elves: &mut HashSet<Point>, round: usize) -> bool {
+    let mut proposals: HashMap<Point, Point> = HashMap::new();
+    let mut counts: HashMap<Point, usize> = HashMap::new();
+    for elf in elves.iter() {
+        let occupied = |offset: &Point| elves.contains(&(*elf + *offset));
+        if !DIRECTIONS.iter().flatten().any(occupied) {
+            continue;
+        }
+        let proposal =
+            (0..4).map(|i| &DIRECTIONS[(round + i) % 4]).find(|cells| !cells.iter().any(occupied));
+        if let Some(cells) = proposal {
+            let destination = *elf + cells[0];
+            proposals.insert(*elf, destination);
+            *counts.entry(destination).or_insert(0) += 1;
+        }
+    }
+    let mut moved = false;
+    for (elf, destination) in proposals {
+        if counts[&destination] == 1 {
+            elves.remove(&elf);
+            elves.insert(destination);
+            moved = true;
+        }
+    }
+    moved
+}
+
+/// Count the empty ground tiles within the smallest rectangle containing every elf, found from
+/// the elves as they are now. As elves spread out the rectangle grows and shifts, so it is never
+/// kept from an earlier round.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day23::*;
+/// let mut elves = input_generator(".....\n..##.\n..#..\n.....\n..##.\n.....");
+/// assert_eq!(bounding_area(&elves), 2 * 4 - 5);
+/// run_round(&mut elves, 0);
+/// assert_eq!(bounding_area(&elves), 2 * 5 - 5);
+/// run_round(&mut elves, 1);
+/// assert_eq!(bounding_area(&elves), 4 * 5 - 5);
+///
+/// let given_elves = "....#..\n..###.#\n#...#.#\n.#...##\n#.###..\n##.#.##\n.#..#..";
+/// let mut elves = input_generator(given_elves);
+/// for round in 0..10 {
+///     run_round(&mut elves, round);
+/// }
+/// assert_eq!(bounding_area(&elves), 110);
+/// ```
+pub fn bounding_area(elves: &HashSet<Point>) -> usize {
+    if elves.is_empty() {
+        return 0;
+    }
+    let min_x = elves.iter().map(|e| e.x).min().unwrap();
+    let max_x = elves.iter().map(|e| e.x).max().unwrap();
+    let min_y = elves.iter().map(|e| e.y).min().unwrap();
+    let max_y = elves.iter().map(|e| e.y).max().unwrap();
+    ((max_x - min_x + 1) * (max_y - min_y + 1)) as usize - elves.len()
+}
+
+/// The number of empty rows and columns left around the elves whenever a [`Bitboard`] is built,
+/// so that it only needs rebuilding once elves have spread this far.
+const MARGIN: i64 = 32;
+
+/// Elves stored as one bitset per row, so that each round checks the neighbors of 64 cells at a
+/// time rather than hashing each neighbor of each elf. Bit `i` of word `w` in a row is the column
+/// `64 * w + i` from the left of the board.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day23::*;
+/// let given_elves = "....#..\n..###.#\n#...#.#\n.#...##\n#.###..\n##.#.##\n.#..#..";
+/// let mut elves = input_generator(given_elves);
+/// let mut board = Bitboard::new(&elves);
+/// for round in 0..20 {
+///     assert_eq!(board.run_round(round), run_round(&mut elves, round));
+///     assert_eq!(board.elves(), elves);
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Bitboard {
+    /// The position of the leftmost column of the topmost row.
+    origin: Point,
+    words: usize,
+    rows: usize,
+    cells: Vec<u64>,
+}
+
+impl Bitboard {
+    /// Build a board holding the given elves, with room to spread in every direction.
+    pub fn new(elves: &HashSet<Point>) -> Bitboard {
+        let min_x = elves.iter().map(|e| e.x).min().unwrap_or(0);
+        let max_x = elves.iter().map(|e| e.x).max().unwrap_or(0);
+        let min_y = elves.iter().map(|e| e.y).min().unwrap_or(0);
+        let max_y = elves.iter().map(|e| e.y).max().unwrap_or(0);
+        let origin = Point::new(min_x - MARGIN, min_y - MARGIN);
+        let words = ((max_x - min_x + 1 + 2 * MARGIN) as usize).div_ceil(64);
+        let rows = (max_y - min_y + 1 + 2 * MARGIN) as usize;
+        let mut board = Bitboard { origin, words, rows, cells: vec![0; words * rows] };
+        for elf in elves {
+            let (x, y) = ((elf.x - origin.x) as usize, (elf.y - origin.y) as usize);
+            board.cells[y * words + x / 64] |= 1 << (x % 64);
+        }
+        board
+    }
+
+    /// Get the position of every elf on the board.
+    pub fn elves(&self) -> HashSet<Point> {
+        let mut elves = HashSet::new();
+        for (i, word) in self.cells.iter().enumerate() {
+            let (y, column) = (i / self.words, (i % self.words) * 64);
+            for bit in (0..64).filter(|bit| word & (1 << bit) != 0) {
+                elves.insert(self.origin + Point::new((column + bit) as i64, y as i64));
+            }
+        }
+        elves
+    }
+
+    /// Whether any elf is on the outermost rows or columns, and could move off the board.
+    fn touches_edge(&self) -> bool {
+        let last_row = (self.rows - 1) * self.words;
+        self.cells[..self.words].iter().any(|w| *w != 0)
+            || self.cells[last_row..].iter().any(|w| *w != 0)
+            || self
+                .cells
+                .chunks(self.words)
+                .any(|row| row[0] & 1 != 0 || row[self.words - 1] & (1 << 63) != 0)
+    }
+
+    /// Get word `i` of row `y` of some cells, where anything outside of the board is empty.
+    fn word(&self, cells: &[u64], y: usize, i: usize) -> u64 {
+        if y < self.rows && i < self.words {
+            cells[y * self.words + i]
+        } else {
+            0
+        }
+    }
+
+    /// Get word `i` of row `y` shifted so that each bit holds the cell `by` columns to its left.
+    fn shifted_right(&self, cells: &[u64], y: usize, i: usize, by: u32) -> u64 {
+        let carry = i.checked_sub(1).map_or(0, |i| self.word(cells, y, i) >> (64 - by));
+        self.word(cells, y, i) << by | carry
+    }
+
+    /// Get word `i` of row `y` shifted so that each bit holds the cell `by` columns to its right.
+    fn shifted_left(&self, cells: &[u64], y: usize, i: usize, by: u32) -> u64 {
+        self.word(cells, y, i) >> by | self.word(cells, y, i + 1) << (64 - by)
+    }
+
+    /// Run a single round as in [`run_round`], returning whether any elf moved.
+    pub fn run_round(&mut self, round: usize) -> bool {
+        if self.touches_edge() {
+            *self = Bitboard::new(&self.elves());
+        }
+        let cells = &self.cells;
+        // The elves proposing to move north, south, west, and east.
+        let mut proposals = [(); 4].map(|_| vec![0; cells.len()]);
+        for y in 0..self.rows {
+            let (above, below) = (y.wrapping_sub(1), y + 1);
+            for i in 0..self.words {
+                let here = |y| self.word(cells, y, i);
+                let left = |y| self.shifted_right(cells, y, i, 1);
+                let right = |y| self.shifted_left(cells, y, i, 1);
+                let free = [
+                    !(left(above) | here(above) | right(above)),
+                    !(left(below) | here(below) | right(below)),
+                    !(left(above) | left(y) | left(below)),
+                    !(right(above) | right(y) | right(below)),
+                ];
+                let alone = free.iter().fold(!0, |a, b| a & b);
+                let mut remaining = here(y) & !alone;
+                for direction in (0..4).map(|d| (round + d) % 4) {
+                    let proposed = remaining & free[direction];
+                    proposals[direction][y * self.words + i] = proposed;
+                    remaining &= !proposed;
+                }
+            }
+        }
+        let [north, south, west, east] = &proposals;
+        let mut next = vec![0; cells.len()];
+        let mut moved = false;
+        for y in 0..self.rows {
+            let (above, below) = (y.wrapping_sub(1), y + 1);
+            for i in 0..self.words {
+                let (from_below, from_above) =
+                    (self.word(north, below, i), self.word(south, above, i));
+                let (from_right, from_left) =
+                    (self.shifted_left(west, y, i, 1), self.shifted_right(east, y, i, 1));
+                let arrived = (from_below ^ from_above) | (from_right ^ from_left);
+                // Elves only ever collide with an elf moving towards them from two cells away.
+                let departed = self.word(north, y, i) & !self.word(south, y.wrapping_sub(2), i)
+                    | self.word(south, y, i) & !self.word(north, y + 2, i)
+                    | self.word(west, y, i) & !self.shifted_right(east, y, i, 2)
+                    | self.word(east, y, i) & !self.shifted_left(west, y, i, 2);
+                moved |= departed != 0;
+                next[y * self.words + i] = cells[y * self.words + i] & !departed | arrived;
+            }
+        }
+        self.cells = next;
+        moved
+    }
+}
+
+/// Run rounds until no elf moves, returning the 1-based number of the first round where no elf
+/// moved.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day23::*;
+/// let given_elves = "....#..\n..###.#\n#...#.#\n.#...##\n#.###..\n##.#.##\n.#..#..";
+/// assert_eq!(rounds_until_stable(&input_generator(given_elves)), 20);
+/// ```
+pub fn rounds_until_stable(elves: &HashSet<Point>) -> usize {
+    let mut board = Bitboard::new(elves);
+    // The state is the number of rounds so far where an elf moved, which is also the number of
+    // the next round until one where nobody moves, so the board itself is never compared.
+    let step = |round: &usize| round + usize::from(board.run_round(*round));
+    let (_, rounds) = iterate_until_stable(0, step, usize::MAX).expect("elves never settled");
+    rounds
+}
+
+#[doc(hidden)]
+#[aoc(day23, part1)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 23, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &HashSet<Point>) -> String {
+    let mut board = Bitboard::new(input);
+    for round in 0..10 {
+        board.run_round(round);
+    }
+    bounding_area(&board.elves()).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day23, part2)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 23, part = 2, input_len = input.len()))
+)]
+pub fn solve_part2(input: &HashSet<Point>) -> String {
+    rounds_until_stable(input).to_string()
+}
+
+solution! {
+    day: 23,
+    generator: input_generator,
+    example: "....#..\n..###.#\n#...#.#\n.#...##\n#.###..\n##.#.##\n.#..#..",
+    solve_part1: "110",
+    solve_part2: "20",
+}
@@ -0,0 +1,335 @@
+use anyhow::{anyhow, Result};
+use aoc_runner_derive::*;
+
+use crate::point::{Direction, Point};
+
+/// A valley surrounded by walls, full of blizzards moving in straight lines and wrapping around
+/// when they hit a wall. Since every blizzard returns to its starting position after
+/// `lcm(width - 2, height - 2)` minutes, the blocked cells for each minute of that period are
+/// computed once up front. The size and blizzards can only be read once the valley is created, so
+/// that they always match the blocked cells computed from them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Valley {
+    width: i64,
+    height: i64,
+    pub start: Point,
+    pub end: Point,
+    blizzards: Vec<(Point, Point)>,
+    blocked: Vec<BlockedGrid>,
+}
+
+/// The cells of a valley covered by at least one blizzard at some minute.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockedGrid {
+    width: i64,
+    cells: Vec<bool>,
+}
+
+impl BlockedGrid {
+    /// Whether a point inside the valley is covered by a blizzard.
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells[(point.y * self.width + point.x) as usize]
+    }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Valley {
+    /// Create a valley from its size, entrance and exit, and the starting position and direction
+    /// of each blizzard.
+    pub fn new(
+        width: i64,
+        height: i64,
+        start: Point,
+        end: Point,
+        blizzards: Vec<(Point, Point)>,
+    ) -> Valley {
+        let mut valley = Valley { width, height, start, end, blizzards, blocked: vec![] };
+        valley.blocked = precompute_blizzards(&valley);
+        valley
+    }
+
+    /// The width of the valley, including its walls.
+    pub fn width(&self) -> i64 {
+        self.width
+    }
+
+    /// The height of the valley, including its walls.
+    pub fn height(&self) -> i64 {
+        self.height
+    }
+
+    /// The starting position and direction of each blizzard.
+    pub fn blizzards(&self) -> &[(Point, Point)] {
+        &self.blizzards
+    }
+
+    /// The number of minutes after which the blizzards repeat.
+    pub fn period(&self) -> usize {
+        let (inner_width, inner_height) = (self.width - 2, self.height - 2);
+        (inner_width * inner_height / gcd(inner_width, inner_height)) as usize
+    }
+
+    /// Whether a point can be stood on at the given minute, being either the entrance, the exit,
+    /// or inside the walls and free from blizzards.
+    pub fn is_open(&self, point: Point, time: usize) -> bool {
+        if point == self.start || point == self.end {
+            return true;
+        }
+        (1..self.width - 1).contains(&point.x)
+            && (1..self.height - 1).contains(&point.y)
+            && !self.blocked[time % self.period()].contains(point)
+    }
+}
+
+/// Get the cells covered by blizzards at the given minute.
+pub fn blizzards_at(valley: &Valley, time: usize) -> BlockedGrid {
+    let (inner_width, inner_height) = (valley.width - 2, valley.height - 2);
+    let mut cells = vec![false; (valley.width * valley.height) as usize];
+    for (p, d) in &valley.blizzards {
+        let x = 1 + (p.x - 1 + d.x * time as i64).rem_euclid(inner_width);
+        let y = 1 + (p.y - 1 + d.y * time as i64).rem_euclid(inner_height);
+        cells[(y * valley.width + x) as usize] = true;
+    }
+    BlockedGrid { width: valley.width, cells }
+}
+
+/// Get the cells covered by blizzards at each minute of the valley's period, after which the
+/// blizzards repeat.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day24::*;
+/// let given_valley = "#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#";
+/// let valley = input_generator(given_valley).unwrap();
+/// let blocked = precompute_blizzards(&valley);
+/// assert_eq!(blocked.len(), 12);
+/// assert_eq!(blizzards_at(&valley, 0), blocked[0]);
+/// assert_eq!(blizzards_at(&valley, valley.period()), blocked[0]);
+/// assert_ne!(blocked[1], blocked[0]);
+/// ```
+pub fn precompute_blizzards(valley: &Valley) -> Vec<BlockedGrid> {
+    (0..valley.period()).map(|t| blizzards_at(valley, t)).collect()
+}
+
+/// Find the minute at which `to` can first be reached, leaving `from` at `start_time`, as in
+/// [`earliest_arrivals`].
+///
+/// # Panics
+///
+/// If there is no way to reach `to`, the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day24::*;
+/// let given_valley = "#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#";
+/// let valley = input_generator(given_valley).unwrap();
+/// let there = traverse(&valley, 0, valley.start, valley.end);
+/// assert_eq!(there, 18);
+/// let back = traverse(&valley, there, valley.end, valley.start);
+/// assert_eq!(back, 41);
+/// assert_eq!(traverse(&valley, back, valley.start, valley.end), 54);
+/// ```
+pub fn traverse(valley: &Valley, start_time: usize, from: Point, to: Point) -> usize {
+    earliest_arrivals(valley, &[(from, start_time)], to)[0]
+}
+
+/// Add the travelers in `bits` that haven't already been at a state to the travelers at its cell,
+/// given the travelers that have been at the state and those at the cell. Returns whether the
+/// cell had no travelers before, but does now.
+fn visit(been: &mut [u64], cell: &mut [u64], bits: &[u64]) -> bool {
+    let was_empty = cell.iter().all(|w| *w == 0);
+    for ((been, cell), bits) in been.iter_mut().zip(cell.iter_mut()).zip(bits) {
+        *cell |= bits & !*been;
+        *been |= bits;
+    }
+    was_empty && cell.iter().any(|w| *w != 0)
+}
+
+/// Find the minute at which `goal` can first be reached by each of several travelers, each
+/// leaving from its own position at its own minute. Travelers don't get in each other's way, so
+/// they are searched for together, one minute at a time: each position reached at a minute holds
+/// a bitset of every traveler that could be there, and its moves are checked against the
+/// blizzards once for all of them. A traveler is only dropped from a position it has already
+/// been at in the same minute modulo the blizzard period, as from there it would play out the
+/// same way.
+///
+/// # Panics
+///
+/// If any traveler has no way to reach `goal`, the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day24::*;
+/// let given_valley = "#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#";
+/// let valley = input_generator(given_valley).unwrap();
+/// let starts = [(valley.start, 41), (valley.start, 0)];
+/// assert_eq!(earliest_arrivals(&valley, &starts, valley.end), vec![54, 18]);
+/// assert_eq!(earliest_arrivals(&valley, &starts[1..], valley.end), vec![18]);
+/// assert_eq!(earliest_arrivals(&valley, &[], valley.end), Vec::<usize>::new());
+///
+/// // Travelers leaving together, or from the goal itself, are still told apart.
+/// let starts = [(valley.start, 0), (valley.end, 18), (valley.start, 0)];
+/// assert_eq!(earliest_arrivals(&valley, &starts, valley.end), vec![18, 18, 18]);
+///
+/// let starts = (0..100).map(|t| (valley.start, t)).collect::<Vec<_>>();
+/// let arrivals = earliest_arrivals(&valley, &starts, valley.end);
+/// for (&(from, time), arrival) in starts.iter().zip(arrivals) {
+///     assert_eq!(arrival, traverse(&valley, time, from, valley.end));
+/// }
+/// ```
+pub fn earliest_arrivals(valley: &Valley, starts: &[(Point, usize)], goal: Point) -> Vec<usize> {
+    let period = valley.period();
+    let cells = (valley.width * valley.height) as usize;
+    let words = starts.len().div_ceil(64);
+    let index = |p: Point| (p.y * valley.width + p.x) as usize;
+    let mut arrivals = vec![None; starts.len()];
+    let mut arrived = vec![0u64; words];
+    // The travelers that have been at each cell, for each minute of the period.
+    let mut seen = vec![0u64; cells * period * words];
+    // The travelers at each cell this minute and the next, along with which cells have any.
+    let (mut here, mut next) = (vec![0u64; cells * words], vec![0u64; cells * words]);
+    let (mut occupied, mut next_occupied) = (vec![], vec![]);
+    let mut bits = vec![0u64; words];
+    let Some(mut time) = starts.iter().map(|(_, start_time)| *start_time).min() else {
+        return vec![];
+    };
+    let state = |p: Point, time: usize| ((time % period) * cells + index(p)) * words;
+    loop {
+        for (i, &(from, start_time)) in starts.iter().enumerate() {
+            if start_time == time {
+                bits.fill(0);
+                bits[i / 64] |= 1 << (i % 64);
+                let (been, cell) = (state(from, time), index(from) * words);
+                if visit(&mut seen[been..][..words], &mut here[cell..][..words], &bits) {
+                    occupied.push(from);
+                }
+            }
+        }
+        let at_goal = &here[index(goal) * words..][..words];
+        if at_goal.iter().any(|w| *w != 0) {
+            for (i, arrival) in arrivals.iter_mut().enumerate() {
+                if at_goal[i / 64] & 1 << (i % 64) != 0 {
+                    arrival.get_or_insert(time);
+                }
+            }
+            arrived.iter_mut().zip(at_goal).for_each(|(a, g)| *a |= g);
+        }
+        if arrivals.iter().all(Option::is_some) {
+            break;
+        }
+        if occupied.is_empty() && starts.iter().all(|(_, start_time)| *start_time <= time) {
+            panic!("no path to {goal:?}");
+        }
+        for position in occupied.drain(..) {
+            let cell = &mut here[index(position) * words..][..words];
+            for w in 0..words {
+                bits[w] = cell[w] & !arrived[w];
+                cell[w] = 0;
+            }
+            if bits.iter().all(|w| *w == 0) {
+                continue;
+            }
+            for offset in [(0, 0), (1, 0), (0, 1), (-1, 0), (0, -1)] {
+                let step = position + Point::new(offset.0, offset.1);
+                if !valley.is_open(step, time + 1) {
+                    continue;
+                }
+                let (been, cell) = (state(step, time + 1), index(step) * words);
+                if visit(&mut seen[been..][..words], &mut next[cell..][..words], &bits) {
+                    next_occupied.push(step);
+                }
+            }
+        }
+        std::mem::swap(&mut here, &mut next);
+        std::mem::swap(&mut occupied, &mut next_occupied);
+        time += 1;
+    }
+    arrivals.into_iter().map(|arrival| arrival.unwrap()).collect()
+}
+
+/// Given an input in the form of a walled valley with an entrance in the top wall, an exit in the
+/// bottom wall, and blizzards drawn as `^`, `v`, `<`, and `>`, generate a Valley.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day24::*;
+/// # use aoc::point::Point;
+/// let valley = input_generator("#.###\n#.>.#\n#...#\n###.#").unwrap();
+/// assert_eq!((valley.width(), valley.height()), (5, 4));
+/// assert_eq!((valley.start, valley.end), (Point::new(1, 0), Point::new(3, 3)));
+/// assert_eq!(valley.blizzards(), [(Point::new(2, 1), Point::new(1, 0))]);
+/// assert_eq!(valley.period(), 6);
+/// assert!(!valley.is_open(Point::new(2, 1), 0));
+/// assert!(valley.is_open(Point::new(2, 1), 1));
+/// assert!(!valley.is_open(Point::new(1, 1), 2));
+/// ```
+#[aoc_generator(day24)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 24, input_len = input.len()))
+)]
+pub fn input_generator(input: &str) -> Result<Valley> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let (first, last) = match (lines.first(), lines.last()) {
+        (Some(first), Some(last)) if lines.len() > 2 => (first, last),
+        _ => return Err(anyhow!("Was given a valley with no room")),
+    };
+    let start = first.find('.').ok_or_else(|| anyhow!("Valley has no entrance"))?;
+    let end = last.find('.').ok_or_else(|| anyhow!("Valley has no exit"))?;
+    let mut blizzards = vec![];
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let direction = match c {
+                '^' => Direction::North.delta(),
+                'v' => Direction::South.delta(),
+                '<' => Direction::West.delta(),
+                '>' => Direction::East.delta(),
+                '#' | '.' => continue,
+                c => return Err(anyhow!("Was given an invalid tile: {c}")),
+            };
+            blizzards.push((Point::new(x as i64, y as i64), direction));
+        }
+    }
+    Ok(Valley::new(
+        first.len() as i64,
+        lines.len() as i64,
+        Point::new(start as i64, 0),
+        Point::new(end as i64, lines.len() as i64 - 1),
+        blizzards,
+    ))
+}
+
+#[doc(hidden)]
+#[aoc(day24, part1)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 24, part = 1)))]
+pub fn solve_part1(input: &Valley) -> String {
+    traverse(input, 0, input.start, input.end).to_string()
+}
+
+#[doc(hidden)]
+#[aoc(day24, part2)]
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(day = 24, part = 2)))]
+pub fn solve_part2(input: &Valley) -> String {
+    let there = traverse(input, 0, input.start, input.end);
+    let back = traverse(input, there, input.end, input.start);
+    traverse(input, back, input.start, input.end).to_string()
+}
+
+solution! {
+    day: 24,
+    try_generator: input_generator,
+    example: "#.######\n#>>.<^<#\n#.<..<<#\n#>v.><>#\n#<^v^^>#\n######.#",
+    solve_part1: "18",
+    solve_part2: "54",
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "std")]
+use aoc_runner_derive::*;
+
+use alloc::string::String;
+
+use crate::balanced;
+
+/// The digits of a SNAFU number along with their values.
+const SNAFU_DIGITS: [(char, i128); 5] = [('=', -2), ('-', -1), ('0', 0), ('1', 1), ('2', 2)];
+
+/// Convert a SNAFU number, written in base 5 with the digits `=`, `-`, `0`, `1`, and `2` standing
+/// for -2 through 2, into an integer. Numbers are kept as i128, so that summing many large SNAFU
+/// numbers can't overflow.
+///
+/// # Panics
+///
+/// If the string contains a character that isn't a SNAFU digit, the function will panic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day25::*;
+/// assert_eq!(from_snafu("1=-0-2"), 1747);
+/// assert_eq!(from_snafu("2=-01"), 976);
+/// assert_eq!(from_snafu("1121-1110-1=0"), 314159265);
+/// ```
+pub fn from_snafu(s: &str) -> i128 {
+    balanced::from_balanced(s, 5, &SNAFU_DIGITS)
+}
+
+/// Convert an integer into a SNAFU number. Each base 5 digit of 3 or 4 can't be written directly,
+/// so it's written as -2 or -1 instead, carrying one into the next digit up.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day25::*;
+/// assert_eq!(to_snafu(0), "0");
+/// assert_eq!(to_snafu(3), "1=");
+/// assert_eq!(to_snafu(2022), "1=11-2");
+/// assert_eq!(to_snafu(314159265), "1121-1110-1=0");
+/// for n in 0..1000 {
+///     assert_eq!(from_snafu(&to_snafu(n)), n);
+/// }
+///
+/// let given_numbers = "1=-0-2\n12111\n2=0=\n21\n2=01\n111\n20012\n112\n1=-1=\n1-12\n12\n1=\n122";
+/// let sum = given_numbers.lines().map(from_snafu).sum::<i128>();
+/// assert_eq!(sum, 4890);
+/// assert_eq!(to_snafu(sum), "2=-1=0");
+///
+/// let large = vec![to_snafu(i64::MAX as i128); 3].join("\n");
+/// let sum = large.lines().map(from_snafu).sum::<i128>();
+/// assert!(sum > i64::MAX as i128);
+/// assert_eq!(from_snafu(&to_snafu(sum)), 3 * i64::MAX as i128);
+/// ```
+pub fn to_snafu(n: i128) -> String {
+    balanced::to_balanced(n, 5, &SNAFU_DIGITS)
+}
+
+#[doc(hidden)]
+#[cfg_attr(feature = "std", aoc(day25, part1))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 25, part = 1, input_len = input.len()))
+)]
+pub fn solve_part1(input: &str) -> String {
+    to_snafu(input.lines().map(from_snafu).sum())
+}
+
+solution! {
+    day: 25,
+    generator: String::from,
+    example: "1=-0-2\n12111\n2=0=\n21\n2=01\n111\n20012\n112\n1=-1=\n1-12\n12\n1=\n122",
+    solve_part1: "2=-1=0",
+}
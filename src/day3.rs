@@ -1,6 +1,11 @@
-use std::collections::HashSet;
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
+#[cfg(feature = "std")]
 use aoc_runner_derive::*;
+
+use crate::answer::Answer;
 // use anyhow::{anyhow, Error, Result};
 
 /// Given two strs, find all common chars between them.
@@ -33,11 +38,27 @@ use aoc_runner_derive::*;
 /// );
 /// assert_eq!(similar_chars, vec!['r', 'Z']);
 pub fn common_chars(c1: impl AsRef<str>, c2: impl AsRef<str>) -> String {
-    let hsa: HashSet<char> = HashSet::from_iter(c1.as_ref().chars());
-    let hsb: HashSet<char> = HashSet::from_iter(c2.as_ref().chars());
+    let hsa: BTreeSet<char> = BTreeSet::from_iter(c1.as_ref().chars());
+    let hsb: BTreeSet<char> = BTreeSet::from_iter(c2.as_ref().chars());
     hsa.intersection(&hsb).collect()
 }
 
+/// Given two strs, find all common chars between them, without duplicates and sorted by priority.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day3::*;
+/// assert_eq!(common_items_sorted("aBcAb", "bAzBa"), vec!['a', 'b', 'A', 'B']);
+/// assert_eq!(common_items_sorted("bAzBa", "AbcaBa"), vec!['a', 'b', 'A', 'B']);
+/// assert!(common_items_sorted("abc", "def").is_empty());
+/// ```
+pub fn common_items_sorted(c1: &str, c2: &str) -> Vec<char> {
+    let mut items = common_chars(c1, c2).chars().collect::<Vec<_>>();
+    items.sort_by_key(|ch| (priority(*ch), *ch));
+    items
+}
+
 /// Generate a Priority from a char
 ///
 /// # Example
@@ -78,6 +99,28 @@ pub fn priority(ch: char) -> Option<u32> {
     }
 }
 
+/// Find the char with the given priority, the inverse of [`priority`].
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day3::*;
+/// assert_eq!(char_from_priority(1), Some('a'));
+/// assert_eq!(char_from_priority(27), Some('A'));
+/// assert_eq!(char_from_priority(0), None);
+/// assert_eq!(char_from_priority(53), None);
+/// for p in 1..=52 {
+///     assert_eq!(priority(char_from_priority(p).unwrap()), Some(p));
+/// }
+/// ```
+pub fn char_from_priority(p: u32) -> Option<char> {
+    match p {
+        1..=26 => char::from_u32('a' as u32 + p - 1),
+        27..=52 => char::from_u32('A' as u32 + p - 27),
+        _ => None,
+    }
+}
+
 /// Given an input in the form of lines of chars, split the line equally in half, and place the
 /// first (including the middle char) into the first String, and place the second in the second
 /// String.
@@ -102,16 +145,26 @@ pub fn priority(ch: char) -> Option<u32> {
 ///     ("CrZsJsPPZsGz".to_string(), "wwsLwLmpwMDw".to_string()),
 /// ];
 /// assert_eq!(compartments, expected);
+///
+/// let compartments = input_generator_part1("añb\nñ");
+/// assert_eq!(compartments[0], ("a".to_string(), "ñb".to_string()));
+/// assert_eq!(compartments[1], ("".to_string(), "ñ".to_string()));
 /// ```
-#[aoc_generator(day3, part1)]
+#[cfg_attr(feature = "std", aoc_generator(day3, part1))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 3, part = 1, input_len = input.len()))
+)]
 pub fn input_generator_part1(input: &str) -> Vec<(String, String)> {
     input
         .lines()
         .map(|line| {
-            (
-                line[0..(line.len() / 2)].to_string(),
-                line[(line.len() / 2)..line.len()].to_string(),
-            )
+            // Split by chars rather than bytes, so a line with any multi-byte chars can't be
+            // split in the middle of one.
+            let half = line.chars().count() / 2;
+            let middle = line.char_indices().nth(half).map_or(line.len(), |(i, _)| i);
+            let (c1, c2) = line.split_at(middle);
+            (c1.to_string(), c2.to_string())
         })
         .collect()
 }
@@ -138,39 +191,127 @@ pub fn input_generator_part1(input: &str) -> Vec<(String, String)> {
 ///      "CrZsJsPPZsGzwwsLwLmpwMDw".to_string()]
 /// ];
 /// assert_eq!(rucksacks, expected);
-#[aoc_generator(day3, part2)]
+#[cfg_attr(feature = "std", aoc_generator(day3, part2))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 3, part = 2, input_len = input.len()))
+)]
 pub fn input_generator_part2(input: &str) -> Vec<[String; 3]> {
-    // Note: This function is messy. It allocates two vectors because it needs to iterate over
-    // slices of the first. This isn't *ideal* but it's not *that* bad.
-    let lines = input.lines().map(String::from).collect::<Vec<_>>();
-    lines
-        .chunks(3)
-        .map(|n| match n {
-            [l1, l2, l3] => [l1.clone(), l2.clone(), l3.clone()],
-            v => panic!("bad input: {v:?}"),
-        })
-        .collect()
+    grouped(input).map(|group| group.map(String::from)).collect()
 }
 
-#[doc(hidden)]
-#[aoc(day3, part1)]
-pub fn solve_part1(compartments: &[(String, String)]) -> String {
+/// Group an input in the form of lines of chars into chunks of 3 lines, only holding one chunk
+/// at a time.
+///
+/// # Panics
+///
+/// If the number of lines isn't a multiple of 3, the function will panic on the last chunk.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day3::*;
+/// let mut groups = grouped("a\nb\nc\nd\ne\nf");
+/// assert_eq!(groups.next(), Some(["a", "b", "c"]));
+/// assert_eq!(groups.next(), Some(["d", "e", "f"]));
+/// assert_eq!(groups.next(), None);
+///
+/// let lines = "vJrwpWtwJgWrhcsFMMfFFhFp
+/// jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+/// PmmdzqPrVvPwwTWBwg
+/// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+/// ttgJtRGJQctTZtZT
+/// CrZsJsPPZsGzwwsLwLmpwMDw";
+/// let chunks = lines.lines().collect::<Vec<_>>();
+/// assert!(grouped(lines).eq(chunks.chunks(3).map(|c| [c[0], c[1], c[2]])));
+/// ```
+pub fn grouped(input: &str) -> impl Iterator<Item = [&str; 3]> {
+    let mut lines = input.lines();
+    core::iter::from_fn(move || match (lines.next(), lines.next(), lines.next()) {
+        (Some(l1), Some(l2), Some(l3)) => Some([l1, l2, l3]),
+        (None, _, _) => None,
+        v => panic!("bad input: {v:?}"),
+    })
+}
+
+/// Find the priority of the common char in each pair of compartments, with 0 for any pair that
+/// has no common char.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day3::*;
+/// let lines = "vJrwpWtwJgWrhcsFMMfFFhFp
+/// jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+/// PmmdzqPrVvPwwTWBwg
+/// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+/// ttgJtRGJQctTZtZT
+/// CrZsJsPPZsGzwwsLwLmpwMDw";
+/// let compartments = input_generator_part1(lines);
+/// assert_eq!(priorities_part1(&compartments), vec![16, 38, 42, 22, 20, 19]);
+/// assert_eq!(priorities_part1(&[("ab".to_string(), "cd".to_string())]), vec![0]);
+/// ```
+pub fn priorities_part1(compartments: &[(String, String)]) -> Vec<u32> {
     compartments
         .iter()
-        .filter_map(|(c1, c2)| common_chars(c1, c2).chars().next())
-        .filter_map(priority)
-        .sum::<u32>()
-        .to_string()
+        .map(|(c1, c2)| common_chars(c1, c2).chars().next().and_then(priority).unwrap_or(0))
+        .collect()
 }
 
-#[doc(hidden)]
-#[aoc(day3, part2)]
-pub fn solve_part2(rucksacks: &[[String; 3]]) -> String {
+/// Find the priority of the common char in each group of rucksacks, with 0 for any group that has
+/// no common char.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::day3::*;
+/// let lines = "vJrwpWtwJgWrhcsFMMfFFhFp
+/// jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+/// PmmdzqPrVvPwwTWBwg
+/// wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+/// ttgJtRGJQctTZtZT
+/// CrZsJsPPZsGzwwsLwLmpwMDw";
+/// let rucksacks = input_generator_part2(lines);
+/// assert_eq!(priorities_part2(&rucksacks), vec![18, 52]);
+/// ```
+pub fn priorities_part2(rucksacks: &[[String; 3]]) -> Vec<u32> {
     rucksacks
         .iter()
-        .filter_map(|rs| rs.iter().cloned().reduce(common_chars))
-        .filter_map(|s| s.chars().next())
-        .filter_map(priority)
-        .sum::<u32>()
-        .to_string()
+        .map(|rs| {
+            rs.iter()
+                .cloned()
+                .reduce(common_chars)
+                .and_then(|s| s.chars().next())
+                .and_then(priority)
+                .unwrap_or(0)
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+#[cfg_attr(feature = "std", aoc(day3, part1))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 3, part = 1, input_len = compartments.len()))
+)]
+pub fn solve_part1(compartments: &[(String, String)]) -> Answer {
+    priorities_part1(compartments).iter().sum::<u32>().into()
+}
+
+#[doc(hidden)]
+#[cfg_attr(feature = "std", aoc(day3, part2))]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(day = 3, part = 2, input_len = rucksacks.len()))
+)]
+pub fn solve_part2(rucksacks: &[[String; 3]]) -> Answer {
+    priorities_part2(rucksacks).iter().sum::<u32>().into()
+}
+
+solution! {
+    day: 3,
+    example: "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n\
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw",
+    solve_part1(input_generator_part1): Answer::Int(157),
+    solve_part2(input_generator_part2): Answer::Int(70),
 }
@@ -0,0 +1,129 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// A Fenwick tree, holding a list of values where updating a single value, summing a prefix of
+/// the values, and finding which value a running total falls within all take `O(log n)` time.
+/// With each value counting how many items are at an index, it finds the `k`th item in order.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::fenwick::*;
+/// let mut tree = Fenwick::from(&[3, 0, 2, 5][..]);
+/// assert_eq!(tree.len(), 4);
+/// assert_eq!(tree.prefix_sum(0), 0);
+/// assert_eq!(tree.prefix_sum(3), 5);
+/// assert_eq!(tree.prefix_sum(4), 10);
+///
+/// tree.add(1, 4);
+/// tree.add(3, -5);
+/// assert_eq!(tree.prefix_sum(2), 7);
+/// assert_eq!(tree.prefix_sum(4), 9);
+///
+/// assert!(Fenwick::default().is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fenwick {
+    /// Index `i` holds the sum of the `i & i.wrapping_neg()` values ending at value `i - 1`.
+    tree: Vec<i64>,
+}
+
+impl Fenwick {
+    /// Create a tree of `len` values, all starting at zero.
+    pub fn new(len: usize) -> Fenwick {
+        Fenwick { tree: vec![0; len + 1] }
+    }
+
+    /// The number of values in the tree.
+    pub fn len(&self) -> usize {
+        self.tree.len() - 1
+    }
+
+    /// Whether the tree holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add `delta` to the value at index `i`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is not less than the number of values, the function will panic.
+    pub fn add(&mut self, i: usize, delta: i64) {
+        assert!(i < self.len(), "index {i} out of range for {} values", self.len());
+        let mut i = i + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Sum the first `i` values, being those at indices `0..i`.
+    ///
+    /// # Panics
+    ///
+    /// If `i` is greater than the number of values, the function will panic.
+    pub fn prefix_sum(&self, i: usize) -> i64 {
+        assert!(i <= self.len(), "prefix {i} out of range for {} values", self.len());
+        let (mut i, mut sum) = (i, 0);
+        while i > 0 {
+            sum += self.tree[i];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// Find the index holding the `k`th item counting from 0, where each value is the number of
+    /// items at its index. Returns None if there are no more than `k` items. The values must not
+    /// be negative.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::fenwick::*;
+    /// let mut tree = Fenwick::from(&[2, 0, 1, 3][..]);
+    /// let kth = |tree: &Fenwick| (0..7).map(|k| tree.find_kth(k)).collect::<Vec<_>>();
+    /// let expected = [Some(0), Some(0), Some(2), Some(3), Some(3), Some(3), None];
+    /// assert_eq!(kth(&tree), expected);
+    ///
+    /// tree.add(0, -2);
+    /// tree.add(1, 1);
+    /// let expected = [Some(1), Some(2), Some(3), Some(3), Some(3), None, None];
+    /// assert_eq!(kth(&tree), expected);
+    /// ```
+    pub fn find_kth(&self, k: i64) -> Option<usize> {
+        let (mut position, mut remaining) = (0, k);
+        let mut step = self.len().checked_next_power_of_two()?;
+        while step > 0 {
+            let next = position + step;
+            if next <= self.len() && self.tree[next] <= remaining {
+                position = next;
+                remaining -= self.tree[next];
+            }
+            step /= 2;
+        }
+        (position < self.len()).then_some(position)
+    }
+}
+
+impl Default for Fenwick {
+    /// An empty tree, still holding the unused value at index 0 that [`Fenwick::len`] relies on.
+    fn default() -> Fenwick {
+        Fenwick::new(0)
+    }
+}
+
+impl From<&[i64]> for Fenwick {
+    /// Build a tree holding the given values in `O(n)` time.
+    fn from(values: &[i64]) -> Fenwick {
+        let mut tree = vec![0; values.len() + 1];
+        tree[1..].copy_from_slice(values);
+        for i in 1..tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Fenwick { tree }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::point::Point;
+
+/// The offsets to each of the six cells sharing a face with a cell.
+pub const NEIGHBORS6: [(i32, i32, i32); 6] =
+    [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)];
+
+/// A dense three-dimensional grid of values covering every cell between two corners, inclusive.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::grid::*;
+/// let mut grid = Grid3::new((-1, 0, 0), (1, 2, 3), false);
+/// assert_eq!((grid.min(), grid.max()), ((-1, 0, 0), (1, 2, 3)));
+/// assert_eq!(grid.get((-1, 2, 3)), Some(&false));
+/// assert_eq!(grid.get((2, 0, 0)), None);
+///
+/// grid.set((0, 1, 2), true);
+/// assert_eq!(grid.get((0, 1, 2)), Some(&true));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid3<T> {
+    min: (i32, i32, i32),
+    size: (usize, usize, usize),
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid3<T> {
+    /// Create a grid covering every cell from `min` to `max`, with every cell set to `fill`.
+    ///
+    /// # Panics
+    ///
+    /// If any coordinate of `max` is smaller than that of `min`, the function will panic.
+    pub fn new(min: (i32, i32, i32), max: (i32, i32, i32), fill: T) -> Grid3<T> {
+        let length = |from: i32, to: i32| {
+            usize::try_from(to - from + 1).expect("max should not be below min")
+        };
+        let size = (length(min.0, max.0), length(min.1, max.1), length(min.2, max.2));
+        Grid3 { min, size, cells: vec![fill; size.0 * size.1 * size.2] }
+    }
+}
+
+impl<T> Grid3<T> {
+    /// The corner of the grid with the smallest coordinates.
+    pub fn min(&self) -> (i32, i32, i32) {
+        self.min
+    }
+
+    /// The corner of the grid with the largest coordinates.
+    pub fn max(&self) -> (i32, i32, i32) {
+        let (x, y, z) = self.min;
+        (x + self.size.0 as i32 - 1, y + self.size.1 as i32 - 1, z + self.size.2 as i32 - 1)
+    }
+
+    fn index(&self, (x, y, z): (i32, i32, i32)) -> Option<usize> {
+        let x = usize::try_from(x - self.min.0).ok().filter(|x| *x < self.size.0)?;
+        let y = usize::try_from(y - self.min.1).ok().filter(|y| *y < self.size.1)?;
+        let z = usize::try_from(z - self.min.2).ok().filter(|z| *z < self.size.2)?;
+        Some((z * self.size.1 + y) * self.size.0 + x)
+    }
+
+    /// Whether a cell is within the bounds of the grid.
+    pub fn contains(&self, cell: (i32, i32, i32)) -> bool {
+        self.index(cell).is_some()
+    }
+
+    /// Get the value of a cell, or None if the cell is outside of the grid.
+    pub fn get(&self, cell: (i32, i32, i32)) -> Option<&T> {
+        self.index(cell).map(|i| &self.cells[i])
+    }
+
+    /// Set the value of a cell.
+    ///
+    /// # Panics
+    ///
+    /// If the cell is outside of the grid, the function will panic.
+    pub fn set(&mut self, cell: (i32, i32, i32), value: T) {
+        let i = self.index(cell).expect("cell should be within the grid");
+        self.cells[i] = value;
+    }
+
+    /// Get the cells within the grid sharing a face with the given cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::grid::*;
+    /// let grid = Grid3::new((0, 0, 0), (2, 2, 2), ());
+    /// assert_eq!(grid.neighbors6((0, 0, 0)).count(), 3);
+    /// assert_eq!(grid.neighbors6((1, 0, 0)).count(), 4);
+    /// assert_eq!(grid.neighbors6((1, 1, 0)).count(), 5);
+    ///
+    /// let mut interior = grid.neighbors6((1, 1, 1)).collect::<Vec<_>>();
+    /// interior.sort();
+    /// assert_eq!(
+    ///     interior,
+    ///     [(0, 1, 1), (1, 0, 1), (1, 1, 0), (1, 1, 2), (1, 2, 1), (2, 1, 1)]
+    /// );
+    /// ```
+    pub fn neighbors6(
+        &self,
+        (x, y, z): (i32, i32, i32),
+    ) -> impl Iterator<Item = (i32, i32, i32)> + '_ {
+        NEIGHBORS6
+            .into_iter()
+            .map(move |(dx, dy, dz)| (x + dx, y + dy, z + dz))
+            .filter(|cell| self.contains(*cell))
+    }
+}
+
+/// A dense two-dimensional grid of values, with `(0, 0)` in the top left and `y` increasing
+/// downwards as with [`Point`].
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::grid::*;
+/// # use aoc::point::Point;
+/// let mut grid = Grid::from("ab\nc");
+/// assert_eq!((grid.width(), grid.height()), (2, 2));
+/// assert_eq!(grid.get(Point::new(1, 0)), Some(&'b'));
+/// // Short rows are padded with spaces.
+/// assert_eq!(grid.get(Point::new(1, 1)), Some(&' '));
+/// assert_eq!(grid.get(Point::new(2, 0)), None);
+/// assert_eq!(grid.get(Point::new(0, -1)), None);
+///
+/// grid.set(Point::new(1, 1), 'd');
+/// assert_eq!(grid.get(Point::new(1, 1)), Some(&'d'));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Create a grid of the given size, with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Create a grid from rows of differing lengths, padding the end of shorter rows with `fill`.
+    pub fn from_rows(rows: &[Vec<T>], fill: T) -> Grid<T> {
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut grid = Grid::new(width, rows.len(), fill);
+        for (y, row) in rows.iter().enumerate() {
+            grid.cells[y * width..y * width + row.len()].clone_from_slice(row);
+        }
+        grid
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, point: Point) -> Option<usize> {
+        let x = usize::try_from(point.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(point.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    /// Get the value of a cell, or None if the cell is outside of the grid.
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index(point).map(|i| &self.cells[i])
+    }
+
+    /// Set the value of a cell.
+    ///
+    /// # Panics
+    ///
+    /// If the cell is outside of the grid, the function will panic.
+    pub fn set(&mut self, point: Point, value: T) {
+        let i = self.index(point).expect("cell should be within the grid");
+        self.cells[i] = value;
+    }
+}
+
+impl From<&str> for Grid<char> {
+    /// Create a grid with a cell for each character of each line, padding shorter lines with
+    /// spaces.
+    fn from(s: &str) -> Grid<char> {
+        let rows = s.lines().map(|line| line.chars().collect()).collect::<Vec<_>>();
+        Grid::from_rows(&rows, ' ')
+    }
+}
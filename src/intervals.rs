@@ -0,0 +1,36 @@
+/// Merge a slice of inclusive ranges into a sorted Vec of disjoint ranges. Overlapping ranges are
+/// always merged; ranges that only touch, such as `(1, 3)` and `(4, 6)`, are merged only when
+/// `adjacent` is true.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::intervals::*;
+/// // Overlapping ranges are coalesced.
+/// assert_eq!(merge(&[(5, 8), (1, 6)], false), vec![(1, 8)]);
+///
+/// // Nested ranges collapse into the outer range.
+/// assert_eq!(merge(&[(1, 10), (3, 4), (2, 9)], false), vec![(1, 10)]);
+///
+/// // Disjoint ranges are sorted but kept apart.
+/// assert_eq!(merge(&[(8, 9), (1, 3)], true), vec![(1, 3), (8, 9)]);
+///
+/// // Touching ranges only merge when asked to.
+/// assert_eq!(merge(&[(4, 6), (1, 3)], false), vec![(1, 3), (4, 6)]);
+/// assert_eq!(merge(&[(4, 6), (1, 3)], true), vec![(1, 6)]);
+///
+/// assert_eq!(merge(&[], true), vec![]);
+/// ```
+pub fn merge(ranges: &[(i64, i64)], adjacent: bool) -> Vec<(i64, i64)> {
+    let mut sorted = Vec::from(ranges);
+    sorted.sort();
+    let gap = if adjacent { 1 } else { 0 };
+    let mut merged: Vec<(i64, i64)> = vec![];
+    for (start, end) in sorted {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end + gap => *last_end = end.max(*last_end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
@@ -1,7 +1,72 @@
+//! The days and utilities that only compute, without the runner or any IO, also build without
+//! the `std` feature, for use in `no_std` contexts. Check this still holds with:
+//!
+//! ```text
+//! cargo build --lib --no-default-features
+//! ```
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "std")]
 pub mod day1;
 pub mod day2;
 pub mod day3;
+#[cfg(feature = "std")]
+pub mod day13;
+#[cfg(feature = "std")]
+pub mod day14;
+#[cfg(feature = "std")]
+pub mod day15;
+#[cfg(feature = "std")]
+pub mod day16;
+#[cfg(feature = "std")]
+pub mod day17;
+#[cfg(feature = "std")]
+pub mod day18;
+#[cfg(feature = "std")]
+pub mod day19;
+#[cfg(feature = "std")]
+pub mod day20;
+#[cfg(feature = "std")]
+pub mod day21;
+#[cfg(feature = "std")]
+pub mod day22;
+#[cfg(feature = "std")]
+pub mod day23;
+#[cfg(feature = "std")]
+pub mod day24;
+pub mod day25;
+pub mod answer;
+pub mod balanced;
+#[cfg(feature = "std")]
+pub mod collections;
+#[cfg(feature = "std")]
+pub mod cube;
+pub mod fenwick;
+#[cfg(feature = "std")]
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod intervals;
+#[cfg(feature = "std")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod pathfinding;
+#[cfg(feature = "std")]
+pub mod point;
+pub mod simulate;
+#[cfg(feature = "testgen")]
+pub mod testgen;
+#[cfg(feature = "std")]
+pub mod testutil;
+#[cfg(feature = "std")]
+pub mod timing;
+pub mod validate;
 
+#[cfg(feature = "std")]
 aoc_runner_derive::aoc_lib!{
     year = 2022
 }
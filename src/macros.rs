@@ -0,0 +1,103 @@
+/// Generate tests checking a day's solvers against the example from the puzzle. The generator is
+/// given the example input, and each named solver is given the generated input and checked
+/// against its expected answer. Generators returning a `Result` are given as `try_generator`, and
+/// the example is expected to parse. Days with a separate generator for each part name it beside
+/// the solver instead. Each solver is also timed on the example, failing if it takes longer than
+/// [`SOLVER_LIMIT`](crate::timing::SOLVER_LIMIT), or than `limit` for days whose examples are
+/// slow to solve in debug builds.
+///
+/// ```text
+/// solution! {
+///     day: 1,
+///     try_generator: input_generator,
+///     example: "1000\n2000\n3000\n\n4000",
+///     solve_part1: Answer::Int(6000),
+///     solve_part2: Answer::Int(10000),
+/// }
+///
+/// solution! {
+///     day: 2,
+///     example: "A Y\nB X\nC Z",
+///     solve_part1(input_generator_part1): Answer::Int(15),
+///     solve_part2(input_generator_part2): Answer::Int(12),
+/// }
+/// ```
+#[cfg_attr(not(feature = "std"), allow(unused_macros))]
+macro_rules! solution {
+    (
+        day: $day:literal,
+        $(limit: $limit:expr,)?
+        generator: $generator:path,
+        example: $example:expr,
+        $($solver:ident: $expected:expr),+ $(,)?
+    ) => {
+        solution!(@tests $day, [$($limit)?], $($solver($generator($example)): $expected),+);
+    };
+    (
+        day: $day:literal,
+        $(limit: $limit:expr,)?
+        try_generator: $generator:path,
+        example: $example:expr,
+        $($solver:ident: $expected:expr),+ $(,)?
+    ) => {
+        solution!(
+            @tests $day,
+            [$($limit)?],
+            $($solver($generator($example).unwrap()): $expected),+
+        );
+    };
+    (
+        day: $day:literal,
+        $(limit: $limit:expr,)?
+        example: $example:expr,
+        $($solver:ident($generator:path): $expected:expr),+ $(,)?
+    ) => {
+        solution!(@tests $day, [$($limit)?], $($solver($generator($example)): $expected),+);
+    };
+    (@limit) => {
+        crate::timing::SOLVER_LIMIT
+    };
+    (@limit $limit:expr) => {
+        $limit
+    };
+    (@tests $day:literal, [$($limit:expr)?], $($solver:ident($input:expr): $expected:expr),+) => {
+        #[cfg(test)]
+        mod solution {
+            // Days solving the raw input may name nothing from the day in their answers.
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                #[test]
+                fn $solver() {
+                    let input = $input;
+                    let answer = super::$solver(&input);
+                    assert_eq!(answer, $expected, "day {} {}", $day, stringify!($solver));
+                }
+            )+
+
+            mod timing {
+                #[allow(unused_imports)]
+                use super::*;
+
+                const LIMIT: std::time::Duration = solution!(@limit $($limit)?);
+
+                $(
+                    #[test]
+                    fn $solver() {
+                        let input = $input;
+                        let start = std::time::Instant::now();
+                        super::super::$solver(&input);
+                        let elapsed = start.elapsed();
+                        assert!(
+                            elapsed < LIMIT,
+                            "day {} {} took {elapsed:?} on the example, over {LIMIT:?}",
+                            $day,
+                            stringify!($solver),
+                        );
+                    }
+                )+
+            }
+        }
+    };
+}
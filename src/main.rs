@@ -1,3 +1,123 @@
-aoc_runner_derive::aoc_main! {
-    lib = aoc
+use std::error::Error;
+use std::time::Duration;
+
+use aoc::timing::{format_phases, timed};
+use aoc::*;
+use aoc_runner::{ArcStr, Runner};
+
+mod runner {
+    aoc_runner_derive::aoc_main! {
+        lib = aoc
+    }
+
+    /// Run every day against the inputs `aoc_main` builds into the binary. These aren't
+    /// validated, as `aoc_main` reports each day's failure on its own and carries on; use
+    /// `--time` or `--json` to validate each input before it's parsed.
+    pub fn run() {
+        main()
+    }
+}
+
+type Generator = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// Every part that can be run with `--time` or `--json`, by day and part.
+const PARTS: &[(u32, u32, Generator)] = &[
+    (1, 1, Factory::day1_part1),
+    (1, 2, Factory::day1_part2),
+    (2, 1, Factory::day2_part1),
+    (2, 2, Factory::day2_part2),
+    (3, 1, Factory::day3_part1),
+    (3, 2, Factory::day3_part2),
+    (13, 1, Factory::day13_part1),
+    (13, 2, Factory::day13_part2),
+    (14, 1, Factory::day14_part1),
+    (14, 2, Factory::day14_part2),
+    (15, 1, Factory::day15_part1),
+    (15, 2, Factory::day15_part2),
+    (16, 1, Factory::day16_part1),
+    (16, 2, Factory::day16_part2),
+    (17, 1, Factory::day17_part1),
+    (17, 2, Factory::day17_part2),
+    (18, 1, Factory::day18_part1),
+    (18, 2, Factory::day18_part2),
+    (19, 1, Factory::day19_part1),
+    (19, 2, Factory::day19_part2),
+    (20, 1, Factory::day20_part1),
+    (20, 2, Factory::day20_part2),
+    (21, 1, Factory::day21_part1),
+    (21, 2, Factory::day21_part2),
+    (22, 1, Factory::day22_part1),
+    (22, 2, Factory::day22_part2),
+    (23, 1, Factory::day23_part1),
+    (23, 2, Factory::day23_part2),
+    (24, 1, Factory::day24_part1),
+    (24, 2, Factory::day24_part2),
+    (25, 1, Factory::day25_part1),
+];
+
+/// Generate the input for a part from `input/2022`, reporting any failure to generate it.
+fn generate(day: u32, part: u32, generator: Generator) -> Option<(Box<dyn Runner>, Duration)> {
+    let Ok(input) = std::fs::read_to_string(format!("input/2022/day{day}.txt")) else {
+        eprintln!("day{day} part{part}: no input");
+        return None;
+    };
+    if let Err(e) = validate::validate(day, &input) {
+        eprintln!("day{day} part{part}: FAILED while validating: {e}");
+        return None;
+    }
+    match timed("parse", || generator(ArcStr::from(&input))) {
+        (Ok(runner), parse) => Some((runner, parse)),
+        (Err(e), _) => {
+            eprintln!("day{day} part{part}: FAILED while generating: {e}");
+            None
+        }
+    }
+}
+
+/// Time the generator and solver of each part separately.
+fn time_parts() {
+    for (day, part, generator) in PARTS {
+        let Some((runner, parse)) = generate(*day, *part, *generator) else { continue };
+        let label = format!("part{part}");
+        match timed(&label, || runner.try_run()) {
+            (Ok(_), solve) => {
+                let phases = [("parse", parse), (label.as_str(), solve)];
+                println!("{}", format_phases(&format!("day{day}"), &phases));
+            }
+            (Err(e), _) => eprintln!("day{day} part{part}: FAILED while running: {e}"),
+        }
+    }
+}
+
+/// Print the answer to each part as a line of JSON.
+#[cfg(feature = "serde")]
+fn json_parts() {
+    use aoc::answer::AnswerRecord;
+
+    for (day, part, generator) in PARTS {
+        let Some((runner, _)) = generate(*day, *part, *generator) else { continue };
+        match runner.try_run() {
+            Ok(answer) => {
+                let record = AnswerRecord { day: *day, part: *part, answer: answer.to_string() };
+                println!("{}", record.to_json());
+            }
+            Err(e) => eprintln!("day{day} part{part}: FAILED while running: {e}"),
+        }
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn json_parts() {
+    eprintln!("--json requires building with the serde feature");
+    std::process::exit(1);
+}
+
+fn main() {
+    if std::env::args().any(|arg| arg == "--time") {
+        time_parts();
+    } else if std::env::args().any(|arg| arg == "--json") {
+        json_parts();
+    } else {
+        runner::run();
+    }
 }
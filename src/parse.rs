@@ -0,0 +1,67 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+
+/// Extract all signed integers from a string, ignoring any other characters.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::parse::*;
+/// let ints = extract_ints("Sensor at x=2, y=-18: closest beacon is at x=-2, y=15");
+/// assert_eq!(ints, vec![2, -18, -2, 15]);
+/// ```
+pub fn extract_ints(s: &str) -> Vec<i64> {
+    let mut ints = vec![];
+    let mut current: Option<String> = None;
+    for ch in s.chars().chain(std::iter::once(' ')) {
+        match (ch, &mut current) {
+            ('0'..='9', Some(n)) => n.push(ch),
+            ('0'..='9' | '-', None) => current = Some(ch.to_string()),
+            (_, Some(n)) => {
+                if let Ok(i) = n.parse() {
+                    ints.push(i);
+                }
+                current = if ch == '-' { Some(ch.to_string()) } else { None };
+            }
+            _ => {}
+        }
+    }
+    ints
+}
+
+/// The position in the input being parsed, so that a value failing to parse can report where it
+/// came from rather than just why it couldn't be parsed.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::parse::*;
+/// let context = ParseContext { line: 3 };
+/// assert_eq!(context.parse::<u32>("12").unwrap(), 12);
+///
+/// let error = context.parse::<u32>("12a").unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "Was given an invalid value on line 3: \"12a\" (invalid digit found in string)"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseContext {
+    /// The 1-based line number of the input being parsed.
+    pub line: usize,
+}
+
+impl ParseContext {
+    /// Parse a token found on this line, attaching the line and the token to any error.
+    pub fn parse<T>(&self, token: &str) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token.parse().map_err(|e| {
+            anyhow!("Was given an invalid value on line {}: {token:?} ({e})", self.line)
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Compute the shortest distance between every pair of `n` nodes, given a list of directed
+/// `(from, to, weight)` edges. The returned matrix is indexed as `[from][to]`, and pairs with no
+/// path between them are left at a sentinel of `u32::MAX / 2`, so that two sentinels can be
+/// added together without overflowing.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::pathfinding::*;
+/// let edges = [(0, 1, 4), (1, 2, 1), (0, 2, 7), (2, 3, 2)];
+/// let distances = floyd_warshall(5, &edges);
+/// assert_eq!(distances[0][1], 4);
+/// // Going through 1 is shorter than the direct edge.
+/// assert_eq!(distances[0][2], 5);
+/// assert_eq!(distances[0][3], 7);
+/// assert_eq!(distances[3][3], 0);
+/// // Edges are directed, and node 4 is not connected at all.
+/// assert_eq!(distances[3][0], u32::MAX / 2);
+/// assert_eq!(distances[0][4], u32::MAX / 2);
+/// ```
+pub fn floyd_warshall(n: usize, edges: &[(usize, usize, u32)]) -> Vec<Vec<u32>> {
+    let mut distances = vec![vec![u32::MAX / 2; n]; n];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[i] = 0;
+    }
+    for (from, to, weight) in edges {
+        distances[*from][*to] = distances[*from][*to].min(*weight);
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let through = distances[i][k] + distances[k][j];
+                if through < distances[i][j] {
+                    distances[i][j] = through;
+                }
+            }
+        }
+    }
+    distances
+}
+
+/// Find the cost of the cheapest path from `start` to any node satisfying `is_goal`, where
+/// `neighbors` gives each node reachable from a node along with the cost of moving there. The
+/// `heuristic` guides the search towards the goal, and must never overestimate the remaining
+/// cost for the result to be the cheapest; a heuristic of zero makes this Dijkstra's algorithm.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::pathfinding::*;
+/// # use aoc::point::Point;
+/// let heightmap = ["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"];
+/// let height = |p: &Point| -> Option<u8> {
+///     let row = heightmap.get(usize::try_from(p.y).ok()?)?.as_bytes();
+///     match *row.get(usize::try_from(p.x).ok()?)? {
+///         b'S' => Some(b'a'),
+///         b'E' => Some(b'z'),
+///         h => Some(h),
+///     }
+/// };
+/// let (start, goal) = (Point::new(0, 0), Point::new(5, 2));
+/// let neighbors = |p: &Point| {
+///     let current = height(p).unwrap();
+///     [(1, 0), (0, 1), (-1, 0), (0, -1)]
+///         .into_iter()
+///         .map(|(x, y)| *p + Point::new(x, y))
+///         .filter(|n| height(n).is_some_and(|h| h <= current + 1))
+///         .map(|n| (n, 1))
+///         .collect::<Vec<_>>()
+/// };
+/// let distance = |p: &Point| p.manhattan(&goal) as u32;
+/// assert_eq!(astar(start, |p| *p == goal, neighbors, distance), Some(31));
+/// assert_eq!(astar(start, |p| *p == goal, neighbors, |_| 0), Some(31));
+/// assert_eq!(astar(start, |p| p.x < 0, neighbors, |_| 0), None);
+/// ```
+pub fn astar<N, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut neighbors: impl FnMut(&N) -> I,
+    heuristic: impl Fn(&N) -> u32,
+) -> Option<u32>
+where
+    N: Clone + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, start))]);
+    while let Some(Reverse((_, cost, node))) = queue.pop() {
+        if is_goal(&node) {
+            return Some(cost);
+        }
+        // A cheaper path to this node has already been found.
+        if costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                queue.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+/// Find the cost of the cheapest path from `start` to any node satisfying `goal`, where
+/// `neighbors` gives each node reachable from a node along with the cost of moving there. This is
+/// [`astar`] without a heuristic.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::pathfinding::*;
+/// // Going straight from 'a' to 'd' takes the fewest steps, but going around is cheaper.
+/// let edges = [('a', 'd', 10), ('a', 'b', 1), ('b', 'c', 1), ('c', 'd', 1), ('d', 'e', 2)];
+/// let neighbors = |n: &char| {
+///     edges.iter().filter(|e| e.0 == *n).map(|e| (e.1, e.2)).collect::<Vec<_>>()
+/// };
+/// assert_eq!(dijkstra('a', neighbors, |n| *n == 'd'), Some(3));
+/// assert_eq!(dijkstra('a', neighbors, |n| *n == 'e'), Some(5));
+/// assert_eq!(dijkstra('d', neighbors, |n| *n == 'a'), None);
+///
+/// let unweighted = |n: &char| neighbors(n).into_iter().map(|(n, _)| n);
+/// assert_eq!(bfs_path('a', |n| *n == 'd', unweighted), Some(vec!['a', 'd']));
+/// ```
+pub fn dijkstra<N>(
+    start: N,
+    neighbors: impl FnMut(&N) -> Vec<(N, u32)>,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<u32>
+where
+    N: Clone + Eq + Hash + Ord,
+{
+    astar(start, goal, neighbors, |_| 0)
+}
+
+/// Find a shortest path from `start` to any node satisfying `is_goal`, where every step to one of
+/// a node's `neighbors` costs the same. The path includes both the start and the goal, so a path
+/// of `n` steps has `n + 1` nodes.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::pathfinding::*;
+/// # use aoc::point::Point;
+/// let grid = ["..#.", "..#.", "...."];
+/// let open = |p: &Point| {
+///     let row = usize::try_from(p.y).ok().and_then(|y| grid.get(y));
+///     let tile = row.and_then(|r| r.as_bytes().get(usize::try_from(p.x).ok()?));
+///     tile == Some(&b'.')
+/// };
+/// let neighbors = |p: &Point| {
+///     [(1, 0), (0, 1), (-1, 0), (0, -1)]
+///         .into_iter()
+///         .map(|(x, y)| *p + Point::new(x, y))
+///         .filter(open)
+///         .collect::<Vec<_>>()
+/// };
+/// let (start, goal) = (Point::new(0, 0), Point::new(3, 0));
+/// let path = bfs_path(start, |p| *p == goal, neighbors).unwrap();
+/// let weighted = |p: &Point| neighbors(p).into_iter().map(|n| (n, 1));
+/// let distance = astar(start, |p| *p == goal, weighted, |_| 0).unwrap();
+/// assert_eq!(path.len() as u32, distance + 1);
+/// assert_eq!((path.first(), path.last()), (Some(&start), Some(&goal)));
+/// assert!(path.windows(2).all(|w| w[0].manhattan(&w[1]) == 1));
+/// assert_eq!(bfs_path(start, |p| p.x < 0, neighbors), None);
+/// ```
+pub fn bfs_path<N, I>(
+    start: N,
+    is_goal: impl Fn(&N) -> bool,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut came_from: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(Some(previous)) = came_from.get(path.last()?) {
+                path.push(previous.clone());
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbors(&node) {
+            if !came_from.contains_key(&next) {
+                came_from.insert(next.clone(), Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,152 @@
+/// A point on a two-dimensional grid, where `y` increases downwards. The default point is the
+/// origin.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::point::Point;
+/// assert_eq!(Point::default(), Point::new(0, 0));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Get the Manhattan distance between two points.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::point::*;
+    /// assert_eq!(Point::new(1, 2).manhattan(&Point::new(-2, 6)), 7);
+    /// ```
+    pub fn manhattan(&self, other: &Point) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+}
+
+impl std::ops::Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+/// One of the four cardinal directions on a grid, where North is towards decreasing `y`.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::point::*;
+/// let mut direction = Direction::North;
+/// for expected in [Direction::East, Direction::South, Direction::West, Direction::North] {
+///     direction = direction.turn_right();
+///     assert_eq!(direction, expected);
+/// }
+/// assert_eq!(Direction::North.turn_left().turn_left(), Direction::South);
+/// assert_eq!(Direction::East.turn_left().turn_right(), Direction::East);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// The direction after turning 90° counterclockwise.
+    pub fn turn_left(&self) -> Direction {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    /// The direction after turning 90° clockwise.
+    pub fn turn_right(&self) -> Direction {
+        self.turn_left().opposite()
+    }
+
+    /// The direction after turning around.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::point::*;
+    /// assert_eq!(Direction::North.opposite(), Direction::South);
+    /// assert_eq!(Direction::West.opposite(), Direction::East);
+    /// assert_eq!(Direction::East.turn_right().turn_right(), Direction::East.opposite());
+    /// ```
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+        }
+    }
+
+    /// The offset of a single step in this direction.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::point::*;
+    /// assert_eq!(Direction::North.delta(), Point::new(0, -1));
+    /// assert_eq!(Direction::East.delta(), Point::new(1, 0));
+    /// assert_eq!(Direction::South.delta(), Point::new(0, 1));
+    /// assert_eq!(Direction::West.delta(), Point::new(-1, 0));
+    /// let back = Direction::East.delta() + Direction::East.opposite().delta();
+    /// assert_eq!(back, Point::default());
+    /// ```
+    pub fn delta(&self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::South => Point::new(0, 1),
+            Direction::West => Point::new(-1, 0),
+        }
+    }
+
+    /// The direction for a day 22 facing, where 0 is East and each facing after it is a turn
+    /// to the right. Facings wrap around, so 4 is East again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use aoc::point::*;
+    /// assert_eq!(Direction::from_facing(1), Direction::South);
+    /// assert_eq!(Direction::from_facing(7), Direction::North);
+    /// for facing in 0..4 {
+    ///     assert_eq!(Direction::from_facing(facing).facing(), facing);
+    /// }
+    /// ```
+    pub fn from_facing(facing: u8) -> Direction {
+        match facing % 4 {
+            0 => Direction::East,
+            1 => Direction::South,
+            2 => Direction::West,
+            _ => Direction::North,
+        }
+    }
+
+    /// The day 22 facing for this direction, as in [`Direction::from_facing`].
+    pub fn facing(&self) -> u8 {
+        match self {
+            Direction::East => 0,
+            Direction::South => 1,
+            Direction::West => 2,
+            Direction::North => 3,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+/// Repeatedly apply a step to a state until the step stops changing it, returning the final state
+/// along with the number of steps taken, including the last step that changed nothing. If the
+/// state is still changing after `max_iters` steps, `None` is returned instead, so that settling
+/// on the last allowed step can be told apart from running out of steps.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::simulate::*;
+/// // 100, 50, 25, 12, 6, 3, 1, 0, then 0 again on the eighth step.
+/// assert_eq!(iterate_until_stable(100, |n| n / 2, 1000), Some((0, 8)));
+/// assert_eq!(iterate_until_stable(100, |n| n / 2, 8), Some((0, 8)));
+/// assert_eq!(iterate_until_stable(100, |n| n / 2, 7), None);
+/// assert_eq!(iterate_until_stable(0, |n| n / 2, 1000), Some((0, 1)));
+///
+/// let mut calls = 0;
+/// iterate_until_stable(100, |n| { calls += 1; n / 2 }, 1000);
+/// assert_eq!(calls, 8);
+/// ```
+pub fn iterate_until_stable<S: PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    max_iters: usize,
+) -> Option<(S, usize)> {
+    let mut state = initial;
+    for iteration in 1..=max_iters {
+        let next = step(&state);
+        if next == state {
+            return Some((next, iteration));
+        }
+        state = next;
+    }
+    None
+}
@@ -0,0 +1,60 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Generate a day1 input for the given number of elves, each carrying up to `max_snacks` snacks.
+/// The same seed always generates the same input, so large inputs can be recreated for
+/// benchmarking rather than committed.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::testgen::*;
+/// # use aoc::day1::*;
+/// let input = random_calories(2022, 100, 5);
+/// assert_eq!(input, random_calories(2022, 100, 5));
+/// assert_ne!(input, random_calories(2023, 100, 5));
+///
+/// let boxes = input_generator(&input).unwrap();
+/// assert_eq!(boxes.len(), 100);
+/// assert!(boxes.iter().all(|b| b.total() <= 5 * 10000));
+/// ```
+pub fn random_calories(seed: u64, elves: usize, max_snacks: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..elves)
+        .map(|_| {
+            let snacks = rng.gen_range(1..=max_snacks.max(1));
+            (0..snacks).map(|_| rng.gen_range(1..=10000).to_string()).collect::<Vec<_>>().join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Generate a day20 input of `count` numbers between -10000 and 10000, with repeats but only a
+/// single zero, as the puzzle's inputs have.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::testgen::*;
+/// # use aoc::day20::*;
+/// let input = random_numbers(2022, 5000);
+/// assert_eq!(input, random_numbers(2022, 5000));
+///
+/// let numbers = input_generator(&input).unwrap();
+/// assert_eq!(numbers.len(), 5000);
+/// assert_eq!(numbers.iter().filter(|n| **n == 0).count(), 1);
+/// assert!(numbers.iter().all(|n| (-10000..=10000).contains(n)));
+/// ```
+pub fn random_numbers(seed: u64, count: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let zero = rng.gen_range(0..count.max(1));
+    (0..count)
+        .map(|i| {
+            let sign = if rng.gen() { 1 } else { -1 };
+            let n: i64 = if i == zero { 0 } else { rng.gen_range(1..=10000) };
+            sign * n
+        })
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+/// Run an old and a new version of a function over each input, checking both give the same
+/// result, such as when rewriting a solver to be faster.
+///
+/// # Panics
+///
+/// If the two versions give different results for any input, the function will panic, showing
+/// the input and both results.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::testutil::*;
+/// let inputs = ["1 2 3", "", "10 -4"];
+/// let sum = |s: &str| s.split_whitespace().map(|n| n.parse::<i32>().unwrap()).sum::<i32>();
+/// let folded = |s: &str| s.split_whitespace().fold(0, |a, n| a + n.parse::<i32>().unwrap());
+/// assert_same(&inputs, sum, folded);
+/// ```
+///
+/// ```rust,should_panic
+/// # use aoc::testutil::*;
+/// // Panics with: input 2 gave different results
+/// //   input: "10 -4"
+/// //     old: 6
+/// //     new: 14
+/// let sum = |s: &str| s.split_whitespace().map(|n| n.parse::<i32>().unwrap()).sum::<i32>();
+/// let absolute = |s: &str| s.split_whitespace().map(|n| n.parse::<i32>().unwrap().abs()).sum();
+/// assert_same(&["1 2 3", "10 -4"], sum, absolute);
+/// ```
+pub fn assert_same<F, G, T>(inputs: &[&str], old: F, new: G)
+where
+    F: Fn(&str) -> T,
+    G: Fn(&str) -> T,
+    T: PartialEq + Debug,
+{
+    for (i, input) in inputs.iter().enumerate() {
+        let (old, new) = (old(input), new(input));
+        assert!(
+            old == new,
+            "input {} gave different results\n  input: {input:?}\n    old: {old:?}\n    new: \
+             {new:?}",
+            i + 1
+        );
+    }
+}
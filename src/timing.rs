@@ -0,0 +1,47 @@
+use std::time::{Duration, Instant};
+
+/// How long a solver may take on its puzzle's example before the tests generated by `solution!`
+/// fail, unless the day gives its own `limit`. Most examples take well under a millisecond even in
+/// debug builds, so this only catches a solver gone pathologically slow, such as by becoming
+/// quadratic, rather than measuring it; the benches are for that.
+pub const SOLVER_LIMIT: Duration = Duration::from_millis(50);
+
+/// Run a phase of a solution, such as parsing or solving, returning its result along with how
+/// long it took. With the `tracing` feature, the phase runs within a `timed` span recording the
+/// label as its `phase`, so that it can be told apart from the other phases of the same part.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::timing::*;
+/// # use std::time::Duration;
+/// let (value, duration) = timed("parse", || 6 * 7);
+/// assert_eq!(value, 42);
+/// assert!(duration >= Duration::ZERO);
+/// ```
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub fn timed<T>(label: &str, f: impl FnOnce() -> T) -> (T, Duration) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("timed", phase = label).entered();
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Format the durations of each phase for a day, in milliseconds.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::timing::*;
+/// # use std::time::Duration;
+/// let phases = [("parse", Duration::from_micros(120)), ("part1", Duration::from_micros(30))];
+/// assert_eq!(format_phases("day1", &phases), "day1 parse: 0.12ms, part1: 0.03ms");
+/// ```
+pub fn format_phases(day: &str, phases: &[(&str, Duration)]) -> String {
+    let phases = phases
+        .iter()
+        .map(|(label, duration)| format!("{label}: {:.2}ms", duration.as_secs_f64() * 1000.0))
+        .collect::<Vec<_>>();
+    format!("{day} {}", phases.join(", "))
+}
@@ -0,0 +1,70 @@
+use anyhow::{anyhow, Result};
+
+/// Whether every character of a non-empty line is one of the given characters.
+fn only(line: &str, chars: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| chars.contains(c))
+}
+
+/// Cheaply check that an input looks like the input for the given day, line by line, so that
+/// giving a solver the wrong day's input fails with an error saying so rather than a confusing
+/// answer or a panic deep within a generator. Passing this doesn't mean the input will parse.
+///
+/// # Example
+///
+/// ```rust
+/// # use aoc::validate::*;
+/// let given_plays = "A Y\nB X\nC Z";
+/// assert!(validate(2, given_plays).is_ok());
+///
+/// let error = validate(3, given_plays).unwrap_err();
+/// assert_eq!(error.to_string(), "day3: line 1 doesn't look like a line of letters: \"A Y\"");
+///
+/// let error = validate(2, "vJrwpWtwJgWrhcsFMMfFFhFp").unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     "day2: line 1 doesn't look like `[ABC] [XYZ]`: \"vJrwpWtwJgWrhcsFMMfFFhFp\""
+/// );
+///
+/// assert_eq!(validate(2, "").unwrap_err().to_string(), "day2: input is empty");
+/// assert!(validate(4, given_plays).is_err());
+/// ```
+pub fn validate(day: u32, input: &str) -> Result<()> {
+    let (shape, valid): (&str, fn(&str) -> bool) = match day {
+        1 => ("a number or a blank line", |l| l.chars().all(|c| c.is_ascii_digit())),
+        2 => ("`[ABC] [XYZ]`", |l| matches!(l.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z'])),
+        3 => ("a line of letters", |l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphabetic())),
+        13 => ("a packet or a blank line", |l| {
+            l.is_empty() || (l.starts_with('[') && l.ends_with(']'))
+        }),
+        14 => ("a path of `x,y` points", |l| only(l, "0123456789,-> ")),
+        15 => ("a sensor", |l| l.starts_with("Sensor at x=")),
+        16 => ("a valve", |l| l.starts_with("Valve ")),
+        17 => ("a jet pattern", |l| only(l, "<>")),
+        18 => ("`x,y,z`", |l| {
+            l.split(',').count() == 3 && l.split(',').all(|n| n.parse::<i32>().is_ok())
+        }),
+        19 => ("a blueprint", |l| l.starts_with("Blueprint ")),
+        20 => ("a number", |l| l.parse::<i64>().is_ok()),
+        21 => ("`name: job`", |l| {
+            l.split_once(": ").is_some_and(|(name, job)| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase()) && !job.is_empty()
+            })
+        }),
+        22 => ("a row of the map, a blank line, or a path", |l| {
+            l.is_empty() || only(l, " .#") || only(l, "0123456789LR")
+        }),
+        23 => ("a row of `#` and `.`", |l| only(l, "#.")),
+        24 => ("a row of the valley", |l| only(l, "#.<>^v")),
+        25 => ("a SNAFU number", |l| only(l, "=-012")),
+        _ => return Err(anyhow!("Was given an invalid day: {day}")),
+    };
+    if input.trim().is_empty() {
+        return Err(anyhow!("day{day}: input is empty"));
+    }
+    for (i, line) in input.lines().enumerate() {
+        if !valid(line) {
+            return Err(anyhow!("day{day}: line {} doesn't look like {shape}: {line:?}", i + 1));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! Run the `aoc` binary against the inputs in `tests/fixtures`, which only has the example for
+//! day1, so every other day reports that it has no input and is skipped.
+#![cfg(feature = "serde")]
+
+use std::process::Command;
+
+use aoc::answer::AnswerRecord;
+
+#[test]
+fn json_answers() {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("--json")
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .output()
+        .expect("the aoc binary runs");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let records = String::from_utf8(output.stdout)
+        .expect("output is UTF-8")
+        .lines()
+        .map(|line| serde_json::from_str::<AnswerRecord>(line).expect("each line is a record"))
+        .collect::<Vec<_>>();
+    let expected = [(1, 1, "24000"), (1, 2, "45000")].map(|(day, part, answer)| AnswerRecord {
+        day,
+        part,
+        answer: answer.to_string(),
+    });
+    assert_eq!(records, expected);
+}